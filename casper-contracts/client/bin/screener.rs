@@ -0,0 +1,374 @@
+//! `screener` - quote, simulate, decode and build deploys for the TokenFactory
+//!
+//! ```text
+//! screener quote buy --state launch.json --amount 10000000000
+//! screener simulate scenario.json
+//! screener decode launch 0a0000...
+//! screener deploy --factory hash-.. --chain casper-test --key secret_key.pem \
+//!     --proxy-caller proxy_caller.wasm buy --mint hash-.. --cspr 10000000000
+//! ```
+
+use casper_types::{PackageHash, PublicKey, Timestamp, U256, U512};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use odra::prelude::Address;
+use screener_client::decode::{decode_launch, decode_pool, parse_hex};
+use screener_client::quote::{BuyerState, LaunchState};
+use screener_client::simulate::{self, Scenario};
+use screener_client::{ContractCall, FactoryClient, Signer};
+use screener_contracts::token_factory::LaunchConfig;
+use std::error::Error;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type CliResult = Result<(), Box<dyn Error>>;
+
+#[derive(Parser)]
+#[command(name = "screener", about = "ScreenerLand TokenFactory tooling")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Quote a curve trade from a launch state and explain why it would fail
+    Quote {
+        side: Side,
+        /// LaunchState JSON file (decimal string amounts)
+        #[arg(long, conflicts_with = "record")]
+        state: Option<PathBuf>,
+        /// Hex-encoded TokenLaunch record instead of a JSON state
+        #[arg(long)]
+        record: Option<String>,
+        /// BuyerState JSON file (window buys, presale contribution, allowlisting, seller balance and lock)
+        #[arg(long)]
+        buyer: Option<PathBuf>,
+        /// CSPR motes (buy) or tokens (sell) in
+        #[arg(long, value_parser = parse_u256)]
+        amount: U256,
+        #[arg(long, value_parser = parse_u256, default_value = "0")]
+        min_out: U256,
+        /// Block time in seconds (defaults to now)
+        #[arg(long)]
+        now: Option<u64>,
+    },
+    /// Replay a launch and a sequence of trades in OdraVM
+    Simulate {
+        /// Scenario JSON file
+        scenario: PathBuf,
+    },
+    /// Decode a hex-encoded storage record
+    Decode {
+        kind: RecordKind,
+        /// Hex bytes, or @path to a file containing them
+        bytes: String,
+    },
+    /// Print a deploy as JSON (signed with --key, unsigned with --account)
+    Deploy(DeployArgs),
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Side {
+    Buy,
+    Sell,
+    PresaleBuy,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum RecordKind {
+    Launch,
+    Pool,
+}
+
+#[derive(Args)]
+struct DeployArgs {
+    /// Factory package hash (hash-.. or package-..)
+    #[arg(long, value_parser = parse_package)]
+    factory: PackageHash,
+    #[arg(long, default_value = "casper-test")]
+    chain: String,
+    /// PEM secret key; the deploy is signed
+    #[arg(long, conflicts_with = "account", required_unless_present = "account")]
+    key: Option<PathBuf>,
+    /// Public key hex; the deploy is left unsigned
+    #[arg(long)]
+    account: Option<String>,
+    /// proxy_caller.wasm, required for payable calls
+    #[arg(long)]
+    proxy_caller: Option<PathBuf>,
+    /// Gas budget override in motes
+    #[arg(long, value_parser = parse_u512)]
+    payment: Option<U512>,
+    /// Deploy timestamp in milliseconds (defaults to now)
+    #[arg(long)]
+    timestamp: Option<u64>,
+    #[command(subcommand)]
+    call: CallCommand,
+}
+
+#[derive(Subcommand)]
+enum CallCommand {
+    CreateToken {
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        symbol: String,
+        #[arg(long, default_value = "")]
+        uri: String,
+        #[arg(long, value_parser = parse_u512)]
+        initial_buy: Option<U512>,
+        #[command(flatten)]
+        launch_config: LaunchConfigArgs,
+        /// Vesting of the initial-buy tokens in seconds
+        #[arg(long)]
+        creator_lock_duration: Option<u64>,
+        /// Scheduled trading start (block time, seconds)
+        #[arg(long)]
+        trading_starts_at: Option<u64>,
+    },
+    Buy {
+        #[arg(long, value_parser = parse_address)]
+        mint: Address,
+        #[arg(long, value_parser = parse_u512)]
+        cspr: U512,
+        #[arg(long, value_parser = parse_u256, default_value = "0")]
+        min_out: U256,
+        #[arg(long, value_parser = parse_address)]
+        referrer: Option<Address>,
+    },
+    Sell {
+        #[arg(long, value_parser = parse_address)]
+        mint: Address,
+        #[arg(long, value_parser = parse_u256)]
+        tokens: U256,
+        #[arg(long, value_parser = parse_u256, default_value = "0")]
+        min_out: U256,
+        #[arg(long, value_parser = parse_address)]
+        referrer: Option<Address>,
+    },
+    /// CEP-18 approve with the factory as spender
+    Approve {
+        #[arg(long, value_parser = parse_package)]
+        token: PackageHash,
+        #[arg(long, value_parser = parse_u256)]
+        amount: U256,
+    },
+    SwapBuy {
+        #[arg(long, value_parser = parse_address)]
+        token: Address,
+        #[arg(long, value_parser = parse_u512)]
+        cspr: U512,
+        #[arg(long, value_parser = parse_u256, default_value = "0")]
+        min_out: U256,
+        #[arg(long, value_parser = parse_address)]
+        referrer: Option<Address>,
+    },
+    SwapSell {
+        #[arg(long, value_parser = parse_address)]
+        token: Address,
+        #[arg(long, value_parser = parse_u256)]
+        tokens: U256,
+        #[arg(long, value_parser = parse_u256, default_value = "0")]
+        min_out: U256,
+        #[arg(long, value_parser = parse_address)]
+        referrer: Option<Address>,
+    },
+    ClaimCreatorFees {
+        #[arg(long, value_parser = parse_address)]
+        mint: Address,
+    },
+    ClaimCtoFees {
+        #[arg(long, value_parser = parse_address)]
+        token: Address,
+    },
+    ClaimReferralFees,
+    ExecuteCto {
+        #[arg(long, value_parser = parse_address)]
+        mint: Address,
+        #[arg(long, value_parser = parse_u512, default_value = "1000000000000")]
+        price: U512,
+    },
+    ClaimCtoExisting {
+        #[arg(long, value_parser = parse_address)]
+        token: Address,
+        #[arg(long, value_parser = parse_u512, default_value = "1000000000000")]
+        price: U512,
+    },
+}
+
+/// Custom curve parameters; all or none must be given
+#[derive(Args)]
+struct LaunchConfigArgs {
+    #[arg(
+        long,
+        value_parser = parse_u256,
+        requires_all = ["decimals", "virtual_cspr", "virtual_tokens", "graduation_target"]
+    )]
+    total_supply: Option<U256>,
+    #[arg(long, requires = "total_supply")]
+    decimals: Option<u8>,
+    #[arg(long, value_parser = parse_u256, requires = "total_supply")]
+    virtual_cspr: Option<U256>,
+    #[arg(long, value_parser = parse_u256, requires = "total_supply")]
+    virtual_tokens: Option<U256>,
+    #[arg(long, value_parser = parse_u256, requires = "total_supply")]
+    graduation_target: Option<U256>,
+}
+
+impl LaunchConfigArgs {
+    fn into_config(self) -> Option<LaunchConfig> {
+        Some(LaunchConfig {
+            total_supply: self.total_supply?,
+            decimals: self.decimals?,
+            virtual_cspr_reserves: self.virtual_cspr?,
+            virtual_token_reserves: self.virtual_tokens?,
+            graduation_target: self.graduation_target?,
+        })
+    }
+}
+
+fn main() {
+    if let Err(error) = run(Cli::parse()) {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> CliResult {
+    match cli.command {
+        Command::Quote { side, state, record, buyer, amount, min_out, now } => {
+            let state = match (state, record) {
+                (Some(path), _) => serde_json::from_str(&std::fs::read_to_string(path)?)?,
+                (None, Some(record)) => LaunchState::from(&decode_launch(&read_bytes(&record)?)?),
+                (None, None) => return Err("pass --state or --record".into()),
+            };
+            let buyer: BuyerState = match buyer {
+                Some(path) => serde_json::from_str(&std::fs::read_to_string(path)?)?,
+                None => BuyerState::default(),
+            };
+            let now = now.unwrap_or_else(unix_now);
+            let quote = match side {
+                Side::Buy => state.quote_buy(amount, min_out, now, &buyer)?,
+                Side::Sell => state.quote_sell(amount, min_out, now, &buyer)?,
+                Side::PresaleBuy => state.quote_presale_buy(amount, min_out, now, &buyer)?,
+            };
+            println!("{}", serde_json::to_string_pretty(&quote)?);
+        }
+        Command::Simulate { scenario } => {
+            let scenario: Scenario = serde_json::from_str(&std::fs::read_to_string(scenario)?)?;
+            println!("{}", serde_json::to_string_pretty(&simulate::run(&scenario))?);
+        }
+        Command::Decode { kind, bytes } => {
+            let bytes = read_bytes(&bytes)?;
+            match kind {
+                RecordKind::Launch => println!("{:#?}", decode_launch(&bytes)?),
+                RecordKind::Pool => println!("{:#?}", decode_pool(&bytes)?),
+            }
+        }
+        Command::Deploy(args) => deploy(args)?,
+    }
+    Ok(())
+}
+
+fn deploy(args: DeployArgs) -> CliResult {
+    let mut client = FactoryClient::new(args.factory, args.chain);
+    if let Some(path) = args.proxy_caller {
+        client = client.with_proxy_caller_file(path)?;
+    }
+    if let Some(timestamp) = args.timestamp {
+        client = client.with_timestamp(Timestamp::from(timestamp));
+    }
+
+    let mut call = build_call(&client, args.call);
+    if let Some(payment) = args.payment {
+        call = call.with_payment(payment);
+    }
+
+    let deploy = match (args.key, args.account) {
+        (Some(key), _) => client.deploy(&call, &Signer::from_file(key)?)?,
+        (None, Some(account)) => {
+            let account = PublicKey::from_hex(account).map_err(|error| format!("invalid public key: {:?}", error))?;
+            client.unsigned_deploy(&call, account)?
+        }
+        (None, None) => return Err("pass --key or --account".into()),
+    };
+    println!("{}", serde_json::to_string_pretty(&deploy)?);
+    Ok(())
+}
+
+fn build_call(client: &FactoryClient, call: CallCommand) -> ContractCall {
+    match call {
+        CallCommand::CreateToken {
+            name,
+            symbol,
+            uri,
+            initial_buy,
+            launch_config,
+            creator_lock_duration,
+            trading_starts_at,
+        } => client.create_token(
+            &name,
+            &symbol,
+            &uri,
+            initial_buy,
+            launch_config.into_config(),
+            creator_lock_duration,
+            trading_starts_at,
+        ),
+        CallCommand::Buy { mint, cspr, min_out, referrer } => client.buy(mint, cspr, min_out, referrer),
+        CallCommand::Sell { mint, tokens, min_out, referrer } => client.sell(mint, tokens, min_out, referrer),
+        CallCommand::Approve { token, amount } => client.approve(token, amount),
+        CallCommand::SwapBuy { token, cspr, min_out, referrer } => {
+            client.swap_cspr_for_existing(token, cspr, min_out, referrer)
+        }
+        CallCommand::SwapSell { token, tokens, min_out, referrer } => {
+            client.swap_existing_for_cspr(token, tokens, min_out, referrer)
+        }
+        CallCommand::ClaimCreatorFees { mint } => client.claim_creator_fees(mint),
+        CallCommand::ClaimCtoFees { token } => client.claim_cto_fees_existing(token),
+        CallCommand::ClaimReferralFees => client.claim_referral_fees(),
+        CallCommand::ExecuteCto { mint, price } => client.execute_cto(mint, price),
+        CallCommand::ClaimCtoExisting { token, price } => client.claim_cto_existing(token, price),
+    }
+}
+
+/// Hex string or `@path` to a file holding hex
+fn read_bytes(value: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    let hex = match value.strip_prefix('@') {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => value.to_string(),
+    };
+    Ok(parse_hex(&hex)?)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+fn parse_u256(value: &str) -> Result<U256, String> {
+    U256::from_dec_str(value).map_err(|_| format!("invalid amount: {}", value))
+}
+
+fn parse_u512(value: &str) -> Result<U512, String> {
+    U512::from_dec_str(value).map_err(|_| format!("invalid amount: {}", value))
+}
+
+fn parse_address(value: &str) -> Result<Address, String> {
+    Address::from_str(value).map_err(|error| format!("invalid address {}: {:?}", value, error))
+}
+
+fn parse_package(value: &str) -> Result<PackageHash, String> {
+    let hex = value
+        .strip_prefix("package-")
+        .or_else(|| value.strip_prefix("hash-"))
+        .unwrap_or(value);
+    let bytes = parse_hex(hex).map_err(|error| error.to_string())?;
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| format!("package hash must be 32 bytes: {}", value))?;
+    Ok(PackageHash::new(bytes))
+}
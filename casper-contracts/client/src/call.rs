@@ -0,0 +1,165 @@
+use crate::error::ClientError;
+use casper_types::{
+    bytesrepr::{Bytes, ToBytes},
+    runtime_args, ExecutableDeployItem, PackageHash, RuntimeArgs, U512,
+};
+
+/// Gas budget for a direct (non-payable) entrypoint call: 5 CSPR
+pub const DEFAULT_CALL_PAYMENT: u64 = 5_000_000_000;
+/// Gas budget for a payable call (runs the proxy_caller session wasm): 15 CSPR
+pub const DEFAULT_PAYABLE_PAYMENT: u64 = 15_000_000_000;
+/// Gas budget for calls creating launches/pools or calling CEP-18 tokens: 50 CSPR
+pub const DEFAULT_HEAVY_PAYMENT: u64 = 50_000_000_000;
+
+/// Arg names read by Odra's `proxy_caller` wasm
+pub const PACKAGE_HASH_ARG: &str = "package_hash";
+pub const ENTRY_POINT_ARG: &str = "entry_point";
+pub const ARGS_ARG: &str = "args";
+pub const ATTACHED_VALUE_ARG: &str = "attached_value";
+pub const AMOUNT_ARG: &str = "amount";
+
+/// Gas budgets per kind of call, overridable per client
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Payments {
+    pub call: U512,
+    pub payable: U512,
+    pub heavy: U512,
+}
+
+impl Default for Payments {
+    fn default() -> Self {
+        Self {
+            call: U512::from(DEFAULT_CALL_PAYMENT),
+            payable: U512::from(DEFAULT_PAYABLE_PAYMENT),
+            heavy: U512::from(DEFAULT_HEAVY_PAYMENT),
+        }
+    }
+}
+
+/// Which `Payments` budget a call uses unless it is given an explicit one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaymentTier {
+    Call,
+    Payable,
+    Heavy,
+}
+
+/// A typed entrypoint call, ready to be wrapped in a deploy
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContractCall {
+    entry_point: String,
+    args: RuntimeArgs,
+    attached_value: Option<U512>,
+    tier: PaymentTier,
+    /// Explicit gas budget, taking precedence over the tier
+    payment: Option<U512>,
+    /// Called package when it is not the factory (e.g. a CEP-18 approve)
+    target: Option<PackageHash>,
+}
+
+impl ContractCall {
+    /// Non-payable call
+    pub fn new(entry_point: &str, args: RuntimeArgs) -> Self {
+        Self {
+            entry_point: entry_point.to_string(),
+            args,
+            attached_value: None,
+            tier: PaymentTier::Call,
+            payment: None,
+            target: None,
+        }
+    }
+
+    /// Payable call attaching `attached_value` motes
+    pub fn payable(entry_point: &str, args: RuntimeArgs, attached_value: U512) -> Self {
+        Self {
+            attached_value: Some(attached_value),
+            tier: PaymentTier::Payable,
+            ..Self::new(entry_point, args)
+        }
+    }
+
+    /// Use the heavy budget (launch/pool creation, CEP-18 calls)
+    pub fn heavy(mut self) -> Self {
+        self.tier = PaymentTier::Heavy;
+        self
+    }
+
+    /// Override the gas budget (motes)
+    pub fn with_payment(mut self, payment: U512) -> Self {
+        self.payment = Some(payment);
+        self
+    }
+
+    /// Call another package instead of the factory
+    pub fn with_target(mut self, target: PackageHash) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    pub fn entry_point(&self) -> &str {
+        &self.entry_point
+    }
+
+    pub fn args(&self) -> &RuntimeArgs {
+        &self.args
+    }
+
+    pub fn attached_value(&self) -> Option<U512> {
+        self.attached_value
+    }
+
+    pub fn tier(&self) -> PaymentTier {
+        self.tier
+    }
+
+    /// Gas budget with the default `Payments`
+    pub fn payment(&self) -> U512 {
+        self.payment_with(&Payments::default())
+    }
+
+    /// Gas budget: the explicit one, else the tier's budget in `payments`
+    pub fn payment_with(&self, payments: &Payments) -> U512 {
+        self.payment.unwrap_or(match self.tier {
+            PaymentTier::Call => payments.call,
+            PaymentTier::Payable => payments.payable,
+            PaymentTier::Heavy => payments.heavy,
+        })
+    }
+
+    pub fn target(&self) -> Option<PackageHash> {
+        self.target
+    }
+
+    /// Session item calling `package_hash`
+    /// Payable calls need the `proxy_caller` wasm bytes
+    pub fn session(
+        &self,
+        package_hash: PackageHash,
+        proxy_caller: Option<&[u8]>,
+    ) -> Result<ExecutableDeployItem, ClientError> {
+        let package_hash = self.target.unwrap_or(package_hash);
+        match self.attached_value {
+            None => Ok(ExecutableDeployItem::StoredVersionedContractByHash {
+                hash: package_hash,
+                version: None,
+                entry_point: self.entry_point.clone(),
+                args: self.args.clone(),
+            }),
+            Some(attached_value) => {
+                let wasm = proxy_caller.ok_or(ClientError::MissingProxyCaller)?;
+                let args = runtime_args! {
+                    PACKAGE_HASH_ARG => package_hash,
+                    ENTRY_POINT_ARG => self.entry_point.clone(),
+                    ARGS_ARG => Bytes::from(self.args.to_bytes()?),
+                    ATTACHED_VALUE_ARG => attached_value,
+                    AMOUNT_ARG => attached_value,
+                };
+                Ok(ExecutableDeployItem::ModuleBytes {
+                    module_bytes: Bytes::from(wasm.to_vec()),
+                    args,
+                })
+            }
+        }
+    }
+}
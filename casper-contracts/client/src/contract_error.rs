@@ -0,0 +1,73 @@
+use screener_contracts::token_factory::Error;
+
+/// Every `TokenFactory` error in code order (code = position + 1), for naming reverts
+pub fn all() -> [Error; 41] {
+    use Error::*;
+    [
+        TokenAlreadyExists,
+        TokenNotFound,
+        InitialBuyTooLow,
+        SlippageExceeded,
+        InsufficientTokens,
+        Unauthorized,
+        AlreadyGraduated,
+        NotEnoughToGraduate,
+        InsufficientClaimAmount,
+        NoFeesToClaim,
+        TransferFailed,
+        CTONotEligible,
+        CTOPriceTooLow,
+        InsufficientHolders,
+        NotInitialized,
+        PoolNotFound,
+        CTOAlreadyClaimed,
+        InsufficientLiquidity,
+        InvalidLaunchConfig,
+        SniperLimitExceeded,
+        TokensLocked,
+        TradingNotStarted,
+        PresaleNotActive,
+        NotAllowlisted,
+        PresaleCapExceeded,
+        InvalidFeeSplit,
+        CTOAuctionRequired,
+        AuctionNotActive,
+        AuctionActive,
+        BidTooLow,
+        CTOPending,
+        NoPendingCTO,
+        ChallengeWindowOpen,
+        ChallengeWindowClosed,
+        InvalidAttestation,
+        InsufficientShares,
+        PoolDelisted,
+        DepositLocked,
+        InvalidPath,
+        DeadlineExpired,
+        OracleWindowTooLong,
+    ]
+}
+
+/// Contract error reverting with `code`, if any
+pub fn from_code(code: u16) -> Option<Error> {
+    all().into_iter().nth(usize::from(code).checked_sub(1)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes_round_trip() {
+        for error in all() {
+            let code = error as u16;
+            assert_eq!(from_code(code).map(|error| error as u16), Some(code));
+        }
+        assert!(from_code(0).is_none());
+        assert!(matches!(from_code(37), Some(Error::PoolDelisted)));
+
+        // The last variant closes the list; a new one must be appended here too
+        assert_eq!(all().len(), Error::OracleWindowTooLong as usize);
+        assert!(from_code(Error::OracleWindowTooLong as u16 + 1).is_none());
+    }
+}
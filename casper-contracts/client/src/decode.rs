@@ -0,0 +1,110 @@
+use crate::error::ClientError;
+use casper_types::bytesrepr::{self, FromBytes};
+use screener_contracts::token_factory::{LiquidityPool, TokenLaunch};
+
+/// Decode a `TokenLaunch` record (raw bytes or length-prefixed dictionary value)
+pub fn decode_launch(bytes: &[u8]) -> Result<TokenLaunch, ClientError> {
+    decode_record(bytes)
+}
+
+/// Decode a `LiquidityPool` record (raw bytes or length-prefixed dictionary value)
+pub fn decode_pool(bytes: &[u8]) -> Result<LiquidityPool, ClientError> {
+    decode_record(bytes)
+}
+
+/// Decode `T`, accepting a `u32` length prefix as written for `Bytes` values
+pub fn decode_record<T: FromBytes>(bytes: &[u8]) -> Result<T, ClientError> {
+    if let Ok(value) = bytesrepr::deserialize_from_slice(bytes) {
+        return Ok(value);
+    }
+    let (inner, rest) = Vec::<u8>::from_bytes(bytes)?;
+    if !rest.is_empty() {
+        return Err(ClientError::Serialization(bytesrepr::Error::LeftOverBytes));
+    }
+    Ok(bytesrepr::deserialize_from_slice(inner)?)
+}
+
+/// Lowercase hex of `bytes`
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Parse hex, with or without a `0x` prefix
+pub fn parse_hex(value: &str) -> Result<Vec<u8>, ClientError> {
+    let value = value.trim();
+    let value = value.strip_prefix("0x").unwrap_or(value);
+    if value.len() % 2 != 0 {
+        return Err(ClientError::InvalidHex(value.to_string()));
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|index| {
+            u8::from_str_radix(&value[index..index + 2], 16)
+                .map_err(|_| ClientError::InvalidHex(value.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use casper_types::{account::AccountHash, bytesrepr::ToBytes, U256};
+    use odra::prelude::Address;
+    use screener_contracts::token_factory::SniperProtection;
+
+    fn launch() -> TokenLaunch {
+        let creator = Address::Account(AccountHash::new([3u8; 32]));
+        TokenLaunch {
+            mint: Address::Account(AccountHash::new([1u8; 32])),
+            creator,
+            name: "Test".to_string(),
+            symbol: "TEST".to_string(),
+            uri: String::new(),
+            total_supply: U256::from(1_000u32),
+            decimals: 9,
+            graduation_target: U256::from(100u32),
+            virtual_cspr_reserves: U256::from(30u32),
+            virtual_token_reserves: U256::from(1_073u32),
+            real_cspr_reserves: U256::zero(),
+            circulating_supply: U256::zero(),
+            creator_fees_unclaimed: U256::zero(),
+            stories_fees_unclaimed: U256::zero(),
+            created_at: 0,
+            trading_starts_at: 0,
+            trading_opened: true,
+            graduated: false,
+            creator_last_active: 0,
+            market_last_active: 0,
+            website: None,
+            telegram: None,
+            twitter: None,
+            banner_uri: None,
+            holders_count: 0,
+            is_cto: false,
+            sniper_protection: SniperProtection {
+                window: 0,
+                max_tokens_per_wallet: None,
+                max_cspr_per_wallet: None,
+                max_buys_per_block: None,
+            },
+            fee_schedule: None,
+            presale: None,
+            price_cumulative: U256::zero(),
+            price_updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_decode_launch_raw_and_prefixed() {
+        let launch = launch();
+        let raw = launch.to_bytes().unwrap();
+        assert_eq!(decode_launch(&raw).unwrap(), launch);
+
+        let prefixed = raw.to_bytes().unwrap();
+        assert_eq!(decode_launch(&prefixed).unwrap(), launch);
+
+        assert_eq!(parse_hex("0x0aff").unwrap(), vec![0x0a, 0xff]);
+        assert!(parse_hex("abc").is_err());
+        assert_eq!(parse_hex(&encode_hex(&raw)).unwrap(), raw);
+    }
+}
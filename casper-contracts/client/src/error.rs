@@ -0,0 +1,48 @@
+use casper_types::bytesrepr;
+use std::fmt;
+
+/// Errors raised while building or signing deploys
+#[derive(Debug)]
+pub enum ClientError {
+    /// Payable call built without the `proxy_caller` wasm
+    MissingProxyCaller,
+    /// Key file could not be read or parsed
+    InvalidKey(String),
+    /// Runtime args could not be serialized
+    Serialization(bytesrepr::Error),
+    /// Deploy builder rejected the deploy
+    InvalidDeploy(String),
+    /// Malformed hex input
+    InvalidHex(String),
+    /// File could not be read
+    Io(std::io::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::MissingProxyCaller => {
+                write!(f, "payable calls need the proxy_caller wasm")
+            }
+            ClientError::InvalidKey(error) => write!(f, "invalid secret key: {}", error),
+            ClientError::Serialization(error) => write!(f, "serialization failed: {}", error),
+            ClientError::InvalidDeploy(error) => write!(f, "invalid deploy: {}", error),
+            ClientError::InvalidHex(value) => write!(f, "invalid hex: {}", value),
+            ClientError::Io(error) => write!(f, "io error: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<bytesrepr::Error> for ClientError {
+    fn from(error: bytesrepr::Error) -> Self {
+        ClientError::Serialization(error)
+    }
+}
+
+impl From<std::io::Error> for ClientError {
+    fn from(error: std::io::Error) -> Self {
+        ClientError::Io(error)
+    }
+}
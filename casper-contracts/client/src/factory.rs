@@ -0,0 +1,695 @@
+use crate::call::{ContractCall, Payments};
+use crate::error::ClientError;
+use crate::signer::Signer;
+use casper_types::{
+    bytesrepr::Bytes, runtime_args, Deploy, DeployBuilder, PackageHash, PublicKey, SecretKey, TimeDiff, Timestamp,
+    Transaction, U256, U512,
+};
+use odra::prelude::Address;
+use screener_contracts::token_factory::{
+    ActivityKind, FeeRecipient, FeeSchedule, LaunchBounds, LaunchConfig, SniperProtection,
+};
+use std::path::Path;
+
+/// Default deploy TTL: 30 minutes
+const DEFAULT_TTL_MILLIS: u64 = 1_800_000;
+
+/// Typed builders for every `TokenFactory` entrypoint
+///
+/// Builders only encode the call; `deploy` / `unsigned_deploy` turn it into a
+/// Casper deploy for this factory and chain. Nothing touches the network.
+pub struct FactoryClient {
+    package_hash: PackageHash,
+    chain_name: String,
+    proxy_caller: Option<Vec<u8>>,
+    ttl: TimeDiff,
+    timestamp: Option<Timestamp>,
+    payments: Payments,
+}
+
+impl FactoryClient {
+    pub fn new<C: Into<String>>(package_hash: PackageHash, chain_name: C) -> Self {
+        Self {
+            package_hash,
+            chain_name: chain_name.into(),
+            proxy_caller: None,
+            ttl: TimeDiff::from_millis(DEFAULT_TTL_MILLIS),
+            timestamp: None,
+            payments: Payments::default(),
+        }
+    }
+
+    /// `proxy_caller.wasm` bytes, required for payable entrypoints
+    pub fn with_proxy_caller(mut self, wasm: Vec<u8>) -> Self {
+        self.proxy_caller = Some(wasm);
+        self
+    }
+
+    /// Load `proxy_caller.wasm` from disk
+    pub fn with_proxy_caller_file<P: AsRef<Path>>(self, path: P) -> Result<Self, ClientError> {
+        let wasm = std::fs::read(path)?;
+        Ok(self.with_proxy_caller(wasm))
+    }
+
+    pub fn with_ttl(mut self, ttl: TimeDiff) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Fixed deploy timestamp (defaults to now)
+    pub fn with_timestamp(mut self, timestamp: Timestamp) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Gas budgets for calls without an explicit payment
+    pub fn with_payments(mut self, payments: Payments) -> Self {
+        self.payments = payments;
+        self
+    }
+
+    pub fn payments(&self) -> &Payments {
+        &self.payments
+    }
+
+    pub fn package_hash(&self) -> PackageHash {
+        self.package_hash
+    }
+
+    pub fn chain_name(&self) -> &str {
+        &self.chain_name
+    }
+
+    /// Address of the factory (CEP-18 spender for sells and pools)
+    pub fn address(&self) -> Address {
+        Address::Contract(self.package_hash)
+    }
+
+    // ========================================================================
+    // DEPLOYS
+    // ========================================================================
+
+    /// Deploy signed by `signer`
+    pub fn deploy(&self, call: &ContractCall, signer: &Signer) -> Result<Deploy, ClientError> {
+        self.build_deploy(call, signer.public_key().clone(), Some(signer.secret_key()))
+    }
+
+    /// Deploy without approvals, to be signed elsewhere (e.g. a wallet)
+    pub fn unsigned_deploy(
+        &self,
+        call: &ContractCall,
+        account: PublicKey,
+    ) -> Result<Deploy, ClientError> {
+        self.build_deploy(call, account, None)
+    }
+
+    /// Signed deploy wrapped as a transaction (accepted by Casper 2.0 nodes)
+    pub fn transaction(
+        &self,
+        call: &ContractCall,
+        signer: &Signer,
+    ) -> Result<Transaction, ClientError> {
+        Ok(Transaction::Deploy(self.deploy(call, signer)?))
+    }
+
+    fn build_deploy(
+        &self,
+        call: &ContractCall,
+        account: PublicKey,
+        secret_key: Option<&SecretKey>,
+    ) -> Result<Deploy, ClientError> {
+        let session = call.session(self.package_hash, self.proxy_caller.as_deref())?;
+        let mut builder = DeployBuilder::new(self.chain_name.clone(), session)
+            .with_account(account)
+            .with_standard_payment(call.payment_with(&self.payments))
+            .with_ttl(self.ttl);
+        if let Some(timestamp) = self.timestamp {
+            builder = builder.with_timestamp(timestamp);
+        }
+        if let Some(secret_key) = secret_key {
+            builder = builder.with_secret_key(secret_key);
+        }
+        builder
+            .build()
+            .map_err(|error| ClientError::InvalidDeploy(error.to_string()))
+    }
+
+    // ========================================================================
+    // LAUNCHPAD
+    // ========================================================================
+
+    /// Create a launch; `initial_buy` CSPR is attached and spent on the curve
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_token(
+        &self,
+        name: &str,
+        symbol: &str,
+        uri: &str,
+        initial_buy: Option<U512>,
+        launch_config: Option<LaunchConfig>,
+        creator_lock_duration: Option<u64>,
+        trading_starts_at: Option<u64>,
+    ) -> ContractCall {
+        let initial_buy = initial_buy.filter(|amount| !amount.is_zero());
+        let args = runtime_args! {
+            "name" => name.to_string(),
+            "symbol" => symbol.to_string(),
+            "uri" => uri.to_string(),
+            "initial_buy_cspr" => initial_buy.map(to_u256),
+            "launch_config" => launch_config,
+            "creator_lock_duration" => creator_lock_duration,
+            "trading_starts_at" => trading_starts_at,
+        };
+        let call = match initial_buy {
+            Some(amount) => ContractCall::payable("create_token", args, amount),
+            None => ContractCall::new("create_token", args),
+        };
+        call.heavy()
+    }
+
+    /// Buy on the bonding curve with `cspr_amount` motes
+    pub fn buy(
+        &self,
+        mint: Address,
+        cspr_amount: U512,
+        min_tokens_out: U256,
+        referrer: Option<Address>,
+    ) -> ContractCall {
+        let args = runtime_args! {
+            "mint" => mint,
+            "min_tokens_out" => min_tokens_out,
+            "referrer" => referrer,
+        };
+        ContractCall::payable("buy", args, cspr_amount)
+    }
+
+    /// Whitelisted presale buy with the address' merkle proof
+    pub fn presale_buy(
+        &self,
+        mint: Address,
+        cspr_amount: U512,
+        min_tokens_out: U256,
+        proof: Vec<[u8; 32]>,
+    ) -> ContractCall {
+        let args = runtime_args! {
+            "mint" => mint,
+            "min_tokens_out" => min_tokens_out,
+            "proof" => proof,
+        };
+        ContractCall::payable("presale_buy", args, cspr_amount)
+    }
+
+    /// Sell launch tokens back to the curve
+    pub fn sell(
+        &self,
+        mint: Address,
+        tokens_in: U256,
+        min_cspr_out: U256,
+        referrer: Option<Address>,
+    ) -> ContractCall {
+        let args = runtime_args! {
+            "mint" => mint,
+            "tokens_in" => tokens_in,
+            "min_cspr_out" => min_cspr_out,
+            "referrer" => referrer,
+        };
+        ContractCall::new("sell", args)
+    }
+
+    pub fn open_trading(&self, mint: Address) -> ContractCall {
+        ContractCall::new("open_trading", runtime_args! { "mint" => mint })
+    }
+
+    pub fn update_token_info(
+        &self,
+        mint: Address,
+        website: Option<String>,
+        telegram: Option<String>,
+        twitter: Option<String>,
+        banner_uri: Option<String>,
+    ) -> ContractCall {
+        let args = runtime_args! {
+            "mint" => mint,
+            "website" => website,
+            "telegram" => telegram,
+            "twitter" => twitter,
+            "banner_uri" => banner_uri,
+        };
+        ContractCall::new("update_token_info", args)
+    }
+
+    /// Allowlisted presale before `public_trading_at` (creator only)
+    pub fn set_presale(
+        &self,
+        mint: Address,
+        merkle_root: [u8; 32],
+        max_cspr_per_address: U512,
+        public_trading_at: u64,
+    ) -> ContractCall {
+        let args = runtime_args! {
+            "mint" => mint,
+            "merkle_root" => merkle_root,
+            "max_cspr_per_address" => to_u256(max_cspr_per_address),
+            "public_trading_at" => public_trading_at,
+        };
+        ContractCall::new("set_presale", args)
+    }
+
+    /// Split the creator fees of `token`; an empty list clears the split
+    pub fn set_fee_recipients(&self, token: Address, recipients: Vec<FeeRecipient>) -> ContractCall {
+        let args = runtime_args! {
+            "token" => token,
+            "recipients" => recipients,
+        };
+        ContractCall::new("set_fee_recipients", args)
+    }
+
+    pub fn set_fee_receiver(&self, token: Address, fee_receiver: Option<Address>) -> ContractCall {
+        let args = runtime_args! {
+            "token" => token,
+            "fee_receiver" => fee_receiver,
+        };
+        ContractCall::new("set_fee_receiver", args)
+    }
+
+    pub fn record_activity(&self, token: Address, kind: ActivityKind) -> ContractCall {
+        let args = runtime_args! {
+            "token" => token,
+            "kind" => kind,
+        };
+        ContractCall::new("record_activity", args)
+    }
+
+    /// Activity for many tokens from a platform-signed attestation
+    pub fn record_activity_batch(
+        &self,
+        tokens: Vec<Address>,
+        kind: ActivityKind,
+        issued_at: u64,
+        nonce: u64,
+        signature: Bytes,
+    ) -> ContractCall {
+        let args = runtime_args! {
+            "tokens" => tokens,
+            "kind" => kind,
+            "issued_at" => issued_at,
+            "nonce" => nonce,
+            "signature" => signature,
+        };
+        ContractCall::new("record_activity_batch", args).heavy()
+    }
+
+    // ========================================================================
+    // EXISTING TOKEN POOLS
+    // ========================================================================
+
+    /// CEP-18 `approve` letting the factory pull `amount` of `token`
+    /// Needed before selling, creating a pool or adding liquidity
+    pub fn approve(&self, token: PackageHash, amount: U256) -> ContractCall {
+        let args = runtime_args! {
+            "spender" => self.address(),
+            "amount" => amount,
+        };
+        ContractCall::new("approve", args).with_target(token)
+    }
+
+    /// Open a pool; attaches the listing deposit plus the initial CSPR liquidity
+    pub fn create_pool_existing(
+        &self,
+        token_contract: Address,
+        initial_token_amount: U256,
+        cspr_liquidity: U512,
+        listing_deposit: U512,
+    ) -> ContractCall {
+        let args = runtime_args! {
+            "token_contract" => token_contract,
+            "initial_token_amount" => initial_token_amount,
+        };
+        ContractCall::payable("create_pool_existing", args, cspr_liquidity + listing_deposit)
+            .heavy()
+    }
+
+    pub fn add_liquidity(
+        &self,
+        token_contract: Address,
+        cspr_amount: U512,
+        max_token_amount: U256,
+        min_shares: U256,
+    ) -> ContractCall {
+        let args = runtime_args! {
+            "token_contract" => token_contract,
+            "max_token_amount" => max_token_amount,
+            "min_shares" => min_shares,
+        };
+        ContractCall::payable("add_liquidity", args, cspr_amount)
+            .heavy()
+    }
+
+    pub fn remove_liquidity(
+        &self,
+        token_contract: Address,
+        shares: U256,
+        min_cspr_out: U256,
+        min_tokens_out: U256,
+    ) -> ContractCall {
+        let args = runtime_args! {
+            "token_contract" => token_contract,
+            "shares" => shares,
+            "min_cspr_out" => min_cspr_out,
+            "min_tokens_out" => min_tokens_out,
+        };
+        ContractCall::new("remove_liquidity", args).heavy()
+    }
+
+    pub fn swap_cspr_for_existing(
+        &self,
+        token_contract: Address,
+        cspr_amount: U512,
+        min_tokens_out: U256,
+        referrer: Option<Address>,
+    ) -> ContractCall {
+        let args = runtime_args! {
+            "token_contract" => token_contract,
+            "min_tokens_out" => min_tokens_out,
+            "referrer" => referrer,
+        };
+        ContractCall::payable("swap_cspr_for_existing", args, cspr_amount)
+            .heavy()
+    }
+
+    pub fn swap_existing_for_cspr(
+        &self,
+        token_contract: Address,
+        tokens_in: U256,
+        min_cspr_out: U256,
+        referrer: Option<Address>,
+    ) -> ContractCall {
+        let args = runtime_args! {
+            "token_contract" => token_contract,
+            "tokens_in" => tokens_in,
+            "min_cspr_out" => min_cspr_out,
+            "referrer" => referrer,
+        };
+        ContractCall::new("swap_existing_for_cspr", args)
+            .heavy()
+    }
+
+    /// Token → CSPR → token along `path` = [token_in, token_out] (existing or graduated pools)
+    pub fn swap_exact_tokens_for_tokens(
+        &self,
+        path: Vec<Address>,
+        amount_in: U256,
+        min_out: U256,
+        deadline: u64,
+    ) -> ContractCall {
+        let args = runtime_args! {
+            "path" => path,
+            "amount_in" => amount_in,
+            "min_out" => min_out,
+            "deadline" => deadline,
+        };
+        ContractCall::new("swap_exact_tokens_for_tokens", args)
+            .heavy()
+    }
+
+    pub fn claim_pool_deposit(&self, token_contract: Address) -> ContractCall {
+        ContractCall::new("claim_pool_deposit", runtime_args! { "token_contract" => token_contract })
+    }
+
+    /// Delist a spam pool (moderator or platform), burning or refunding the deposit
+    pub fn delist_pool(&self, token_contract: Address, burn_deposit: bool) -> ContractCall {
+        let args = runtime_args! {
+            "token_contract" => token_contract,
+            "burn_deposit" => burn_deposit,
+        };
+        ContractCall::new("delist_pool", args)
+    }
+
+    // ========================================================================
+    // CLAIMS
+    // ========================================================================
+
+    pub fn claim_creator_fees(&self, mint: Address) -> ContractCall {
+        ContractCall::new("claim_creator_fees", runtime_args! { "mint" => mint })
+    }
+
+    pub fn claim_cto_fees_existing(&self, token_contract: Address) -> ContractCall {
+        ContractCall::new("claim_cto_fees_existing", runtime_args! { "token_contract" => token_contract })
+    }
+
+    /// Claim creator/CTO fees of many tokens in one payout
+    pub fn claim_all_fees(&self, mints: Vec<Address>, token_contracts: Vec<Address>) -> ContractCall {
+        let args = runtime_args! {
+            "mints" => mints,
+            "token_contracts" => token_contracts,
+        };
+        ContractCall::new("claim_all_fees", args).heavy()
+    }
+
+    pub fn claim_previous_owner_fees(&self, token: Address) -> ContractCall {
+        ContractCall::new("claim_previous_owner_fees", runtime_args! { "token" => token })
+    }
+
+    pub fn claim_referral_fees(&self) -> ContractCall {
+        ContractCall::new("claim_referral_fees", runtime_args! {})
+    }
+
+    /// Pay `amount` of a launch's stories fees to `claimer` (platform only)
+    pub fn claim_stories_fees(&self, mint: Address, claimer: Address, amount: U512) -> ContractCall {
+        let args = runtime_args! {
+            "mint" => mint,
+            "claimer" => claimer,
+            "amount" => to_u256(amount),
+        };
+        ContractCall::new("claim_stories_fees", args)
+    }
+
+    // ========================================================================
+    // CTO
+    // ========================================================================
+
+    /// Take over an inactive launch, attaching the CTO price
+    pub fn execute_cto(&self, mint: Address, cto_price: U512) -> ContractCall {
+        ContractCall::payable("execute_cto", runtime_args! { "mint" => mint }, cto_price)
+    }
+
+    /// Take over (or reclaim) an existing-token pool, attaching the CTO price
+    pub fn claim_cto_existing(&self, token_contract: Address, cto_price: U512) -> ContractCall {
+        let args = runtime_args! { "token_contract" => token_contract };
+        ContractCall::payable("claim_cto_existing", args, cto_price)
+    }
+
+    pub fn contest_cto(&self, token: Address) -> ContractCall {
+        ContractCall::new("contest_cto", runtime_args! { "token" => token })
+    }
+
+    pub fn finalize_cto(&self, token: Address) -> ContractCall {
+        ContractCall::new("finalize_cto", runtime_args! { "token" => token })
+    }
+
+    /// Open a CTO auction with the first bid
+    pub fn start_cto_auction(&self, token: Address, bid: U512) -> ContractCall {
+        ContractCall::payable("start_cto_auction", runtime_args! { "token" => token }, bid)
+    }
+
+    pub fn bid_cto(&self, token: Address, bid: U512) -> ContractCall {
+        ContractCall::payable("bid_cto", runtime_args! { "token" => token }, bid)
+    }
+
+    pub fn settle_cto_auction(&self, token: Address) -> ContractCall {
+        ContractCall::new("settle_cto_auction", runtime_args! { "token" => token })
+    }
+
+    /// Withdraw outbid CTO auction funds
+    pub fn withdraw_bid_refund(&self) -> ContractCall {
+        ContractCall::new("withdraw_bid_refund", runtime_args! {})
+    }
+
+    // ========================================================================
+    // PLATFORM ADMIN
+    // ========================================================================
+
+    pub fn set_launch_bounds(&self, bounds: LaunchBounds) -> ContractCall {
+        ContractCall::new("set_launch_bounds", runtime_args! { "bounds" => bounds })
+    }
+
+    pub fn set_sniper_protection(&self, protection: SniperProtection) -> ContractCall {
+        ContractCall::new("set_sniper_protection", runtime_args! { "protection" => protection })
+    }
+
+    pub fn set_launch_fee_schedule(&self, schedule: Option<FeeSchedule>) -> ContractCall {
+        ContractCall::new("set_launch_fee_schedule", runtime_args! { "schedule" => schedule })
+    }
+
+    pub fn set_referral_share_bps(&self, share_bps: u32) -> ContractCall {
+        ContractCall::new("set_referral_share_bps", runtime_args! { "share_bps" => share_bps })
+    }
+
+    pub fn set_min_creator_lock_duration(&self, duration: u64) -> ContractCall {
+        ContractCall::new("set_min_creator_lock_duration", runtime_args! { "duration" => duration })
+    }
+
+    pub fn set_lp_fee_bps(&self, fee_bps: u32) -> ContractCall {
+        ContractCall::new("set_lp_fee_bps", runtime_args! { "fee_bps" => fee_bps })
+    }
+
+    pub fn set_pool_listing_config(&self, deposit: U512, deposit_period: u64) -> ContractCall {
+        let args = runtime_args! {
+            "deposit" => to_u256(deposit),
+            "deposit_period" => deposit_period,
+        };
+        ContractCall::new("set_pool_listing_config", args)
+    }
+
+    pub fn set_moderator(&self, moderator: Address, enabled: bool) -> ContractCall {
+        let args = runtime_args! {
+            "moderator" => moderator,
+            "enabled" => enabled,
+        };
+        ContractCall::new("set_moderator", args)
+    }
+
+    pub fn set_activity_attestor(&self, attestor: PublicKey) -> ContractCall {
+        ContractCall::new("set_activity_attestor", runtime_args! { "attestor" => attestor })
+    }
+
+    pub fn set_cto_challenge_period(&self, period: u64) -> ContractCall {
+        ContractCall::new("set_cto_challenge_period", runtime_args! { "period" => period })
+    }
+
+    pub fn set_cto_auction_config(&self, enabled: bool, duration: u64, min_increment_bps: u32) -> ContractCall {
+        let args = runtime_args! {
+            "enabled" => enabled,
+            "duration" => duration,
+            "min_increment_bps" => min_increment_bps,
+        };
+        ContractCall::new("set_cto_auction_config", args)
+    }
+}
+
+/// Motes as the contract's U256 amounts
+fn to_u256(amount: U512) -> U256 {
+    U256::from(amount.as_u128())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call::{ATTACHED_VALUE_ARG, DEFAULT_CALL_PAYMENT, ENTRY_POINT_ARG};
+    use casper_types::{
+        account::AccountHash,
+        bytesrepr::{Bytes, FromBytes},
+        ExecutableDeployItem, RuntimeArgs,
+    };
+
+    const PROXY_CALLER: &[u8] = b"\0asm proxy";
+
+    fn client() -> FactoryClient {
+        FactoryClient::new(PackageHash::new([1u8; 32]), "casper-test")
+            .with_proxy_caller(PROXY_CALLER.to_vec())
+            .with_timestamp(Timestamp::from(1_700_000_000_000u64))
+    }
+
+    fn signer() -> Signer {
+        Signer::new(SecretKey::ed25519_from_bytes([7u8; 32]).unwrap())
+    }
+
+    fn account(byte: u8) -> Address {
+        Address::Account(AccountHash::new([byte; 32]))
+    }
+
+    #[test]
+    fn test_payable_call_goes_through_proxy_caller() {
+        let client = client();
+        let cspr = U512::from(10_000_000_000u64);
+        let call = client.buy(account(2), cspr, U256::from(5u32), Some(account(3)));
+        assert_eq!(call.attached_value(), Some(cspr));
+
+        let session = call.session(client.package_hash(), Some(PROXY_CALLER)).unwrap();
+        let ExecutableDeployItem::ModuleBytes { module_bytes, args } = session else {
+            panic!("payable call must be session wasm");
+        };
+        assert_eq!(module_bytes.as_slice(), PROXY_CALLER);
+        let value: U512 = args.get(ATTACHED_VALUE_ARG).unwrap().clone().into_t().unwrap();
+        assert_eq!(value, cspr);
+        let entry_point: String = args.get(ENTRY_POINT_ARG).unwrap().clone().into_t().unwrap();
+        assert_eq!(entry_point, "buy");
+
+        // Inner args round-trip
+        let inner: Bytes = args.get("args").unwrap().clone().into_t().unwrap();
+        let (inner, _) = RuntimeArgs::from_bytes(&inner).unwrap();
+        assert_eq!(&inner, call.args());
+    }
+
+    #[test]
+    fn test_plain_call_targets_package() {
+        let client = client();
+        let call = client.sell(account(2), U256::from(100u32), U256::zero(), None);
+        assert_eq!(call.payment(), U512::from(DEFAULT_CALL_PAYMENT));
+
+        let session = call.session(client.package_hash(), None).unwrap();
+        let ExecutableDeployItem::StoredVersionedContractByHash { hash, entry_point, .. } = session else {
+            panic!("non-payable call must be a stored call");
+        };
+        assert_eq!(hash, client.package_hash());
+        assert_eq!(entry_point, "sell");
+
+        // Approvals target the token, with the factory as spender
+        let token = PackageHash::new([9u8; 32]);
+        let approve = client.approve(token, U256::one());
+        let spender: Address = approve.args().get("spender").unwrap().clone().into_t().unwrap();
+        assert_eq!(spender, client.address());
+        assert_eq!(approve.target(), Some(token));
+    }
+
+    #[test]
+    fn test_attached_values() {
+        let client = client();
+        let create = client.create_token("Test", "TEST", "https://test.com", None, None, None, None);
+        assert_eq!(create.attached_value(), None);
+        let create = client.create_token("Test", "TEST", "https://test.com", Some(U512::from(7u32)), None, None, None);
+        assert_eq!(create.attached_value(), Some(U512::from(7u32)));
+
+        let pool = client.create_pool_existing(account(4), U256::one(), U512::from(10u32), U512::from(100u32));
+        assert_eq!(pool.attached_value(), Some(U512::from(110u32)));
+    }
+
+    #[test]
+    fn test_signed_and_unsigned_deploys() {
+        let client = client();
+        let signer = signer();
+        let call = client.claim_referral_fees();
+
+        let deploy = client.deploy(&call, &signer).unwrap();
+        assert_eq!(deploy.approvals().len(), 1);
+        assert_eq!(deploy.header().account(), signer.public_key());
+        assert_eq!(deploy.header().chain_name(), "casper-test");
+
+        let unsigned = client.unsigned_deploy(&call, signer.public_key().clone()).unwrap();
+        assert!(unsigned.approvals().is_empty());
+
+        // Payable calls need the proxy wasm
+        let bare = FactoryClient::new(PackageHash::new([1u8; 32]), "casper-test");
+        let buy = bare.buy(account(2), U512::one(), U256::zero(), None);
+        assert!(matches!(bare.deploy(&buy, &signer), Err(ClientError::MissingProxyCaller)));
+    }
+
+    #[test]
+    fn test_payment_overrides() {
+        let payments = Payments {
+            call: U512::from(1u32),
+            payable: U512::from(2u32),
+            heavy: U512::from(3u32),
+        };
+        let client = client().with_payments(payments);
+
+        // Client budgets apply per tier, an explicit payment wins
+        let delist = client.delist_pool(account(4), true);
+        assert_eq!(delist.payment_with(client.payments()), U512::from(1u32));
+        assert!(client.deploy(&delist, &signer()).is_ok());
+        let batch = client.record_activity_batch(vec![account(4)], ActivityKind::Social, 0, 0, Bytes::new());
+        assert_eq!(batch.payment_with(&payments), U512::from(3u32));
+        let batch = batch.with_payment(U512::from(9u32));
+        assert_eq!(batch.payment_with(&payments), U512::from(9u32));
+    }
+}
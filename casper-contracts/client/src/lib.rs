@@ -0,0 +1,36 @@
+//! Host-side client for the ScreenerLand `TokenFactory`
+//!
+//! Builds typed entrypoint calls and turns them into Casper deploys offline,
+//! signed from a PEM key file (or unsigned, for external signing).
+//!
+//! Payable entrypoints cannot be called directly on Casper: they go through
+//! Odra's `proxy_caller` session wasm, which moves the attached CSPR into a
+//! cargo purse before calling the contract. Non-payable entrypoints are stored
+//! contract calls by package hash.
+//!
+//! The `screener` binary (feature `cli`) builds on the same modules: curve quotes
+//! (`quote`), OdraVM replays (`simulate`, feature `sim`), record decoding
+//! (`decode`), revert names (`contract_error`) and deploy JSON.
+//!
+//! ```ignore
+//! let signer = Signer::from_file("secret_key.pem")?;
+//! let client = FactoryClient::new(factory_package, "casper-test")
+//!     .with_proxy_caller_file("wasm/proxy_caller.wasm")?;
+//! let call = client.buy(mint, U512::from(10_000_000_000u64), U256::zero(), None);
+//! let deploy = client.deploy(&call, &signer)?;
+//! ```
+
+pub mod call;
+pub mod contract_error;
+pub mod decode;
+pub mod error;
+pub mod factory;
+pub mod quote;
+pub mod signer;
+#[cfg(feature = "sim")]
+pub mod simulate;
+
+pub use call::{ContractCall, Payments};
+pub use error::ClientError;
+pub use factory::FactoryClient;
+pub use signer::Signer;
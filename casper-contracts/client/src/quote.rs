@@ -0,0 +1,557 @@
+use casper_types::U256;
+use screener_contracts::token_factory::{
+    curve_buy, curve_sell, launch_fee_bps, CurveQuote, FeeSchedule, Presale, SniperProtection, TokenLaunch,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Bonding-curve state needed to quote a trade (amounts as decimal strings)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LaunchState {
+    #[serde(with = "decimal")]
+    pub virtual_cspr_reserves: U256,
+    #[serde(with = "decimal")]
+    pub virtual_token_reserves: U256,
+    #[serde(with = "decimal")]
+    pub total_supply: U256,
+    /// Tokens already issued by the ledger; buys cannot take it past `total_supply`
+    #[serde(default, with = "decimal")]
+    pub circulating_supply: U256,
+    #[serde(default)]
+    pub trading_starts_at: u64,
+    #[serde(default)]
+    pub graduated: bool,
+    #[serde(default)]
+    pub fee_schedule: Option<FeeScheduleState>,
+    #[serde(default)]
+    pub sniper_protection: Option<SniperState>,
+    #[serde(default)]
+    pub presale: Option<PresaleState>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FeeScheduleState {
+    pub start_fee_bps: u32,
+    pub duration: u64,
+}
+
+/// Anti-sniper limits of the launch (see `get_sniper_protection`)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SniperState {
+    pub window: u64,
+    #[serde(default, with = "optional_decimal")]
+    pub max_tokens_per_wallet: Option<U256>,
+    #[serde(default, with = "optional_decimal")]
+    pub max_cspr_per_wallet: Option<U256>,
+    #[serde(default)]
+    pub max_buys_per_block: Option<u32>,
+}
+
+/// Allowlisted presale of the launch (see `get_presale`)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PresaleState {
+    #[serde(with = "decimal")]
+    pub max_cspr_per_address: U256,
+}
+
+/// What the buyer already did on the launch (see `get_window_buys` / `get_presale_contribution`)
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BuyerState {
+    #[serde(default, with = "decimal")]
+    pub window_cspr_spent: U256,
+    #[serde(default, with = "decimal")]
+    pub window_tokens_bought: U256,
+    /// Buys of the launch already in the current block
+    #[serde(default)]
+    pub block_buys: u32,
+    #[serde(default, with = "decimal")]
+    pub presale_contribution: U256,
+    /// Whether the buyer's proof verifies against the presale Merkle root
+    #[serde(default)]
+    pub allowlisted: bool,
+    /// Launch ledger balance of a seller (`get_launch_balance`); sells are not checked against it when absent
+    #[serde(default, with = "optional_decimal")]
+    pub token_balance: Option<U256>,
+    /// Creator lock when the seller is its beneficiary (`get_creator_lock`)
+    #[serde(default)]
+    pub creator_lock: Option<CreatorLockState>,
+}
+
+/// Vesting of a creator's initial buy (see `get_creator_lock`)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreatorLockState {
+    #[serde(with = "decimal")]
+    pub locked_amount: U256,
+    pub start: u64,
+    pub duration: u64,
+}
+
+impl CreatorLockState {
+    /// Part of the lock still unvested at `now` (linear over `duration`, as in the contract)
+    pub fn unvested(&self, now: u64) -> U256 {
+        let elapsed = now.saturating_sub(self.start);
+        if elapsed >= self.duration {
+            return U256::zero();
+        }
+        self.locked_amount - self.locked_amount * U256::from(elapsed) / U256::from(self.duration)
+    }
+}
+
+impl From<&TokenLaunch> for LaunchState {
+    fn from(launch: &TokenLaunch) -> Self {
+        Self {
+            virtual_cspr_reserves: launch.virtual_cspr_reserves,
+            virtual_token_reserves: launch.virtual_token_reserves,
+            total_supply: launch.total_supply,
+            circulating_supply: launch.circulating_supply,
+            trading_starts_at: launch.trading_starts_at,
+            graduated: launch.graduated,
+            fee_schedule: launch.fee_schedule.as_ref().map(|schedule: &FeeSchedule| FeeScheduleState {
+                start_fee_bps: schedule.start_fee_bps,
+                duration: schedule.duration,
+            }),
+            sniper_protection: Some(SniperState::from(&launch.sniper_protection)),
+            presale: launch.presale.as_ref().map(|presale: &Presale| PresaleState {
+                max_cspr_per_address: presale.max_cspr_per_address,
+            }),
+        }
+    }
+}
+
+impl From<&SniperProtection> for SniperState {
+    fn from(protection: &SniperProtection) -> Self {
+        Self {
+            window: protection.window,
+            max_tokens_per_wallet: protection.max_tokens_per_wallet,
+            max_cspr_per_wallet: protection.max_cspr_per_wallet,
+            max_buys_per_block: protection.max_buys_per_block,
+        }
+    }
+}
+
+impl From<&FeeScheduleState> for FeeSchedule {
+    fn from(schedule: &FeeScheduleState) -> Self {
+        FeeSchedule {
+            start_fee_bps: schedule.start_fee_bps,
+            duration: schedule.duration,
+        }
+    }
+}
+
+/// Result of a quoted trade
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Quote {
+    /// Tokens (buy) or net CSPR motes (sell) received
+    #[serde(with = "decimal")]
+    pub amount_out: U256,
+    #[serde(with = "decimal")]
+    pub fee: U256,
+    pub fee_bps: u32,
+    /// Integer spot price after the trade, as in `TokenBought` / `TokenSold`
+    #[serde(with = "decimal")]
+    pub new_price: U256,
+}
+
+/// Why the contract would reject the trade (contract error names)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QuoteError {
+    AlreadyGraduated,
+    TradingNotStarted,
+    SlippageExceeded { amount_out: U256, min_out: U256 },
+    InsufficientLiquidity,
+    SniperLimitExceeded,
+    PresaleNotActive,
+    NotAllowlisted,
+    PresaleCapExceeded,
+    InsufficientTokens,
+    TokensLocked,
+}
+
+impl std::fmt::Display for QuoteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuoteError::AlreadyGraduated => write!(f, "AlreadyGraduated: trading moved to the DEX"),
+            QuoteError::TradingNotStarted => write!(f, "TradingNotStarted: trading_starts_at not reached"),
+            QuoteError::SlippageExceeded { amount_out, min_out } => {
+                write!(f, "SlippageExceeded: would receive {} < min {}", amount_out, min_out)
+            }
+            QuoteError::InsufficientLiquidity => {
+                write!(f, "InsufficientLiquidity: reserves too small or over the launch's total supply")
+            }
+            QuoteError::SniperLimitExceeded => {
+                write!(f, "SniperLimitExceeded: over a wallet or block cap of the protection window")
+            }
+            QuoteError::PresaleNotActive => write!(f, "PresaleNotActive: no presale before trading_starts_at"),
+            QuoteError::NotAllowlisted => write!(f, "NotAllowlisted: buyer is not in the presale allowlist"),
+            QuoteError::PresaleCapExceeded => write!(f, "PresaleCapExceeded: over max_cspr_per_address"),
+            QuoteError::InsufficientTokens => write!(f, "InsufficientTokens: seller holds fewer tokens"),
+            QuoteError::TokensLocked => write!(f, "TokensLocked: sell would dip into the unvested creator lock"),
+        }
+    }
+}
+
+impl std::error::Error for QuoteError {}
+
+impl LaunchState {
+    /// Launch fee at `now` (the contract's decaying schedule)
+    pub fn fee_bps(&self, now: u64) -> u32 {
+        let schedule = self.fee_schedule.as_ref().map(FeeSchedule::from);
+        launch_fee_bps(schedule.as_ref(), self.trading_starts_at, now)
+    }
+
+    /// Quote a curve buy of `cspr_in` motes at `now` by `buyer`
+    pub fn quote_buy(
+        &self,
+        cspr_in: U256,
+        min_out: U256,
+        now: u64,
+        buyer: &BuyerState,
+    ) -> Result<Quote, QuoteError> {
+        self.check_tradable(now)?;
+        let quote = self.curve_buy_quote(cspr_in, min_out, now)?;
+        self.check_sniper_limits(cspr_in, quote.amount_out, now, buyer)?;
+        Ok(quote)
+    }
+
+    /// Quote a `presale_buy` of `cspr_in` motes at `now` by `buyer`
+    pub fn quote_presale_buy(
+        &self,
+        cspr_in: U256,
+        min_out: U256,
+        now: u64,
+        buyer: &BuyerState,
+    ) -> Result<Quote, QuoteError> {
+        if self.graduated {
+            return Err(QuoteError::AlreadyGraduated);
+        }
+        let presale = self
+            .presale
+            .as_ref()
+            .filter(|_| now < self.trading_starts_at)
+            .ok_or(QuoteError::PresaleNotActive)?;
+        if !buyer.allowlisted {
+            return Err(QuoteError::NotAllowlisted);
+        }
+        if buyer.presale_contribution + cspr_in > presale.max_cspr_per_address {
+            return Err(QuoteError::PresaleCapExceeded);
+        }
+        let quote = self.curve_buy_quote(cspr_in, min_out, now)?;
+        self.check_sniper_limits(cspr_in, quote.amount_out, now, buyer)?;
+        Ok(quote)
+    }
+
+    /// Quote a curve sell of `tokens_in` at `now` by `seller`
+    pub fn quote_sell(
+        &self,
+        tokens_in: U256,
+        min_out: U256,
+        now: u64,
+        seller: &BuyerState,
+    ) -> Result<Quote, QuoteError> {
+        if tokens_in.is_zero() {
+            return Err(QuoteError::InsufficientTokens);
+        }
+        self.check_tradable(now)?;
+        check_seller_balance(tokens_in, now, seller)?;
+        self.check_reserves()?;
+        let fee_bps = self.fee_bps(now);
+        let quote = curve_sell(self.virtual_cspr_reserves, self.virtual_token_reserves, tokens_in, fee_bps);
+        to_quote(quote, fee_bps, min_out)
+    }
+
+    fn curve_buy_quote(&self, cspr_in: U256, min_out: U256, now: u64) -> Result<Quote, QuoteError> {
+        self.check_reserves()?;
+        let fee_bps = self.fee_bps(now);
+        let quote = curve_buy(self.virtual_cspr_reserves, self.virtual_token_reserves, cspr_in, fee_bps);
+        // The ledger never issues more than the supply
+        if self.circulating_supply + quote.amount_out > self.total_supply {
+            return Err(QuoteError::InsufficientLiquidity);
+        }
+        to_quote(quote, fee_bps, min_out)
+    }
+
+    /// The curve math divides by both virtual reserves
+    fn check_reserves(&self) -> Result<(), QuoteError> {
+        if self.virtual_cspr_reserves.is_zero() || self.virtual_token_reserves.is_zero() {
+            return Err(QuoteError::InsufficientLiquidity);
+        }
+        Ok(())
+    }
+
+    /// Per-wallet and per-block caps while the protection window is open
+    fn check_sniper_limits(
+        &self,
+        cspr_in: U256,
+        tokens_out: U256,
+        now: u64,
+        buyer: &BuyerState,
+    ) -> Result<(), QuoteError> {
+        let protection = match &self.sniper_protection {
+            Some(protection) if now < self.trading_starts_at + protection.window => protection,
+            _ => return Ok(()),
+        };
+        let exceeded = protection
+            .max_cspr_per_wallet
+            .is_some_and(|max| buyer.window_cspr_spent + cspr_in > max)
+            || protection
+                .max_tokens_per_wallet
+                .is_some_and(|max| buyer.window_tokens_bought + tokens_out > max)
+            || protection.max_buys_per_block.is_some_and(|max| buyer.block_buys >= max);
+        if exceeded {
+            return Err(QuoteError::SniperLimitExceeded);
+        }
+        Ok(())
+    }
+
+    fn check_tradable(&self, now: u64) -> Result<(), QuoteError> {
+        if self.graduated {
+            return Err(QuoteError::AlreadyGraduated);
+        }
+        if now < self.trading_starts_at {
+            return Err(QuoteError::TradingNotStarted);
+        }
+        Ok(())
+    }
+}
+
+/// Balance and creator-lock checks of the contract's `sell`, when the seller's state is known
+fn check_seller_balance(tokens_in: U256, now: u64, seller: &BuyerState) -> Result<(), QuoteError> {
+    let Some(balance) = seller.token_balance else {
+        return Ok(());
+    };
+    if tokens_in > balance {
+        return Err(QuoteError::InsufficientTokens);
+    }
+    if let Some(lock) = &seller.creator_lock {
+        if balance - tokens_in < lock.unvested(now) {
+            return Err(QuoteError::TokensLocked);
+        }
+    }
+    Ok(())
+}
+
+fn to_quote(quote: CurveQuote, fee_bps: u32, min_out: U256) -> Result<Quote, QuoteError> {
+    if quote.amount_out < min_out {
+        return Err(QuoteError::SlippageExceeded { amount_out: quote.amount_out, min_out });
+    }
+    Ok(Quote {
+        amount_out: quote.amount_out,
+        fee: quote.fee,
+        fee_bps,
+        new_price: quote.virtual_cspr_reserves / quote.virtual_token_reserves,
+    })
+}
+
+/// U256 as a decimal string
+pub mod decimal {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        U256::from_dec_str(&value).map_err(|_| serde::de::Error::custom(format!("invalid amount: {}", value)))
+    }
+}
+
+/// Optional U256 as a decimal string
+pub mod optional_decimal {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<U256>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_str(&value.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<U256>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(value) => U256::from_dec_str(&value)
+                .map(Some)
+                .map_err(|_| serde::de::Error::custom(format!("invalid amount: {}", value))),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> LaunchState {
+        serde_json::from_str(
+            r#"{
+                "virtual_cspr_reserves": "30000000000",
+                "virtual_token_reserves": "800240000000000000",
+                "total_supply": "1000000000000000000",
+                "trading_starts_at": 100,
+                "fee_schedule": { "start_fee_bps": 1000, "duration": 600 }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_quote_buy_and_sell() {
+        let state = state();
+        let cspr_in = U256::from(10_000_000_000u64);
+
+        // Fee decays from 10% to 1% over the schedule
+        assert_eq!(state.fee_bps(100), 1_000);
+        assert_eq!(state.fee_bps(400), 550);
+        assert_eq!(state.fee_bps(700), 100);
+
+        let buy = state.quote_buy(cspr_in, U256::zero(), 700, &BuyerState::default()).unwrap();
+        assert_eq!(buy.fee, U256::from(100_000_000u64));
+        let k = state.virtual_cspr_reserves * state.virtual_token_reserves;
+        let new_cspr = state.virtual_cspr_reserves + cspr_in - buy.fee;
+        assert_eq!(buy.amount_out, state.virtual_token_reserves - k / new_cspr);
+
+        let sell = state.quote_sell(buy.amount_out, U256::zero(), 700, &BuyerState::default()).unwrap();
+        assert!(sell.amount_out < cspr_in);
+    }
+
+    #[test]
+    fn test_quote_explains_failures() {
+        let mut state = state();
+        let cspr_in = U256::from(10_000_000_000u64);
+        assert_eq!(state.quote_buy(cspr_in, U256::zero(), 50, &BuyerState::default()), Err(QuoteError::TradingNotStarted));
+
+        let quote = state.quote_buy(cspr_in, U256::zero(), 700, &BuyerState::default()).unwrap();
+        let min_out = quote.amount_out + U256::one();
+        assert_eq!(
+            state.quote_buy(cspr_in, min_out, 700, &BuyerState::default()),
+            Err(QuoteError::SlippageExceeded { amount_out: quote.amount_out, min_out })
+        );
+
+        // The ledger never issues more than the supply
+        let mut sold_out = state.clone();
+        sold_out.circulating_supply = sold_out.total_supply;
+        assert_eq!(sold_out.quote_buy(cspr_in, U256::zero(), 700, &BuyerState::default()), Err(QuoteError::InsufficientLiquidity));
+        let mut empty = state.clone();
+        empty.virtual_cspr_reserves = U256::zero();
+        assert_eq!(empty.quote_buy(cspr_in, U256::zero(), 700, &BuyerState::default()), Err(QuoteError::InsufficientLiquidity));
+
+        state.graduated = true;
+        assert_eq!(
+            state.quote_sell(U256::one(), U256::zero(), 700, &BuyerState::default()),
+            Err(QuoteError::AlreadyGraduated)
+        );
+    }
+
+    #[test]
+    fn test_quote_sell_checks_balance_and_creator_lock() {
+        let state = state();
+        let tokens = U256::from(1_000_000_000u64);
+        let none = BuyerState::default();
+        assert_eq!(state.quote_sell(U256::zero(), U256::zero(), 700, &none), Err(QuoteError::InsufficientTokens));
+
+        let holder = BuyerState { token_balance: Some(tokens), ..BuyerState::default() };
+        assert!(state.quote_sell(tokens, U256::zero(), 700, &holder).is_ok());
+        assert_eq!(
+            state.quote_sell(tokens + U256::one(), U256::zero(), 700, &holder),
+            Err(QuoteError::InsufficientTokens)
+        );
+
+        // Half of the lock vests by t=600; the unvested half must stay in the balance
+        let creator = BuyerState {
+            creator_lock: Some(CreatorLockState { locked_amount: tokens, start: 100, duration: 1_000 }),
+            ..holder
+        };
+        let half = tokens / 2;
+        assert!(state.quote_sell(half, U256::zero(), 600, &creator).is_ok());
+        assert_eq!(state.quote_sell(half + U256::one(), U256::zero(), 600, &creator), Err(QuoteError::TokensLocked));
+        assert!(state.quote_sell(tokens, U256::zero(), 1_100, &creator).is_ok());
+    }
+
+    #[test]
+    fn test_quote_applies_sniper_and_presale_rules() {
+        let mut state = state();
+        let cspr_in = U256::from(10_000_000_000u64);
+        state.sniper_protection = Some(SniperState {
+            window: 300,
+            max_tokens_per_wallet: None,
+            max_cspr_per_wallet: Some(U256::from(15_000_000_000u64)),
+            max_buys_per_block: Some(2),
+        });
+        state.presale = Some(PresaleState { max_cspr_per_address: cspr_in });
+
+        // Wallet and block caps inside the window, lifted after it
+        let spent = BuyerState { window_cspr_spent: cspr_in, ..BuyerState::default() };
+        assert_eq!(state.quote_buy(cspr_in, U256::zero(), 200, &spent), Err(QuoteError::SniperLimitExceeded));
+        let busy = BuyerState { block_buys: 2, ..BuyerState::default() };
+        assert_eq!(state.quote_buy(cspr_in, U256::zero(), 200, &busy), Err(QuoteError::SniperLimitExceeded));
+        assert!(state.quote_buy(cspr_in, U256::zero(), 400, &spent).is_ok());
+
+        // Presale: allowlist and cap before trading_starts_at, closed after
+        let outsider = BuyerState::default();
+        let member = BuyerState { allowlisted: true, ..BuyerState::default() };
+        let capped = BuyerState { presale_contribution: U256::one(), ..member.clone() };
+        assert_eq!(state.quote_presale_buy(cspr_in, U256::zero(), 50, &outsider), Err(QuoteError::NotAllowlisted));
+        assert!(state.quote_presale_buy(cspr_in, U256::zero(), 50, &member).is_ok());
+        assert_eq!(state.quote_presale_buy(cspr_in, U256::zero(), 50, &capped), Err(QuoteError::PresaleCapExceeded));
+        assert_eq!(state.quote_presale_buy(cspr_in, U256::zero(), 200, &member), Err(QuoteError::PresaleNotActive));
+    }
+
+    #[test]
+    fn test_quote_matches_contract_getters() {
+        use odra::host::Deployer;
+        use casper_types::U512;
+        use screener_contracts::token_factory::{Error, TokenCreated, TokenFactory, TokenFactoryInitArgs};
+
+        let env = odra_test::env();
+        env.set_caller(env.get_account(0));
+        let mut factory = TokenFactory::deploy(
+            &env,
+            TokenFactoryInitArgs {
+                platform_wallet: env.get_account(0),
+                stories_pool: env.get_account(1),
+                screener_token: env.get_account(2),
+                burn_address: Some(env.get_account(9)),
+            },
+        );
+        factory.set_launch_fee_schedule(Some(FeeSchedule { start_fee_bps: 1_000, duration: 600 }));
+        factory.set_sniper_protection(SniperProtection {
+            window: 300,
+            max_tokens_per_wallet: None,
+            max_cspr_per_wallet: Some(U256::from(15_000_000_000u64)),
+            max_buys_per_block: None,
+        });
+        env.set_caller(env.get_account(3));
+        factory.create_token("Test".to_string(), "TEST".to_string(), String::new(), None, None, None, None);
+        let mint = env.get_event::<TokenCreated, _>(&factory, -1).unwrap().mint;
+        env.advance_block_time(200);
+
+        let state = LaunchState::from(&factory.get_token_launch(mint).unwrap());
+        let now = env.block_time();
+        let cspr_in = U256::from(10_000_000_000u64);
+        assert_eq!(state.fee_bps(now), factory.get_current_fee_bps(mint));
+        let buy = state.quote_buy(cspr_in, U256::zero(), now, &BuyerState::default()).unwrap();
+        assert_eq!(buy.amount_out, factory.calculate_buy(mint, cspr_in));
+        let sell = state.quote_sell(buy.amount_out, U256::zero(), now, &BuyerState::default()).unwrap();
+        assert_eq!(sell.amount_out, factory.calculate_sell(mint, buy.amount_out));
+
+        // A second buy would break the window's wallet cap, on both sides
+        let buyer = env.get_account(4);
+        env.set_caller(buyer);
+        factory.with_tokens(U512::from(10_000_000_000u64)).buy(mint, U256::zero(), None);
+        let window_buys = factory.get_window_buys(mint, buyer);
+        let spent = BuyerState {
+            window_cspr_spent: window_buys.cspr_spent,
+            window_tokens_bought: window_buys.tokens_bought,
+            ..BuyerState::default()
+        };
+        let state = LaunchState::from(&factory.get_token_launch(mint).unwrap());
+        assert_eq!(state.quote_buy(cspr_in, U256::zero(), now, &spent), Err(QuoteError::SniperLimitExceeded));
+        let result = factory.with_tokens(U512::from(10_000_000_000u64)).try_buy(mint, U256::zero(), None);
+        assert_eq!(result, Err(Error::SniperLimitExceeded.into()));
+
+        // Selling past the ledger balance fails on both sides
+        let seller = BuyerState { token_balance: Some(factory.get_launch_balance(mint, buyer)), ..spent };
+        let too_many = factory.get_launch_balance(mint, buyer) + U256::one();
+        assert_eq!(state.quote_sell(too_many, U256::zero(), now, &seller), Err(QuoteError::InsufficientTokens));
+        assert_eq!(factory.try_sell(mint, too_many, U256::zero(), None), Err(Error::InsufficientTokens.into()));
+    }
+}
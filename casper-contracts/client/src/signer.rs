@@ -0,0 +1,40 @@
+use crate::error::ClientError;
+use casper_types::{PublicKey, SecretKey};
+use odra::prelude::Address;
+use std::path::Path;
+
+/// Account key used to sign deploys
+pub struct Signer {
+    secret_key: SecretKey,
+    public_key: PublicKey,
+}
+
+impl Signer {
+    pub fn new(secret_key: SecretKey) -> Self {
+        let public_key = PublicKey::from(&secret_key);
+        Self {
+            secret_key,
+            public_key,
+        }
+    }
+
+    /// Load a PEM secret key (ed25519 or secp256k1), e.g. `secret_key.pem`
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ClientError> {
+        let secret_key = SecretKey::from_file(path)
+            .map_err(|error| ClientError::InvalidKey(error.to_string()))?;
+        Ok(Self::new(secret_key))
+    }
+
+    pub fn secret_key(&self) -> &SecretKey {
+        &self.secret_key
+    }
+
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Address the contract sees as `caller`
+    pub fn address(&self) -> Address {
+        Address::Account(self.public_key.to_account_hash())
+    }
+}
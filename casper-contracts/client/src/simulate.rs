@@ -0,0 +1,288 @@
+use crate::contract_error;
+use crate::quote::{decimal, optional_decimal};
+use casper_types::{U256, U512};
+use odra::casper_event_standard::EventInstance;
+use odra::casper_types::bytesrepr::FromBytes;
+use odra::host::{Deployer, HostEnv, HostRef};
+use odra::prelude::OdraError;
+use screener_contracts::security::SecurityError;
+use screener_contracts::token_factory::{
+    LaunchConfig, TokenBought, TokenCreated, TokenFactory, TokenFactoryHostRef,
+    TokenFactoryInitArgs, TokenSold,
+};
+use serde::{Deserialize, Serialize};
+
+/// A launch followed by a sequence of trades, replayed in OdraVM
+///
+/// Accounts are OdraVM test account indexes; 0, 1, 2 and 9 are the platform,
+/// stories pool, SCREENER token and burn address.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Scenario {
+    #[serde(default)]
+    pub launch: LaunchParams,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct LaunchParams {
+    pub creator: usize,
+    #[serde(default, with = "motes")]
+    pub initial_buy: U256,
+    #[serde(default)]
+    pub creator_lock_duration: Option<u64>,
+    /// Delay (seconds) between launch and trading
+    #[serde(default)]
+    pub trading_delay: Option<u64>,
+}
+
+impl Default for LaunchParams {
+    fn default() -> Self {
+        Self {
+            creator: 3,
+            initial_buy: U256::zero(),
+            creator_lock_duration: None,
+            trading_delay: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+    /// Advance block time by seconds
+    Advance(u64),
+    Buy {
+        account: usize,
+        #[serde(with = "motes")]
+        cspr: U256,
+        #[serde(default, with = "decimal")]
+        min_out: U256,
+    },
+    Sell {
+        account: usize,
+        #[serde(with = "decimal")]
+        tokens: U256,
+        #[serde(default, with = "decimal")]
+        min_out: U256,
+    },
+}
+
+/// Outcome of one step
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct StepReport {
+    pub step: usize,
+    pub action: String,
+    pub block_time: u64,
+    /// Contract error name when the call reverted
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "optional_decimal")]
+    pub amount_out: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_bps: Option<u32>,
+}
+
+/// Run `scenario` on a fresh factory; the launch is step 0
+pub fn run(scenario: &Scenario) -> Vec<StepReport> {
+    let env = odra_test::env();
+    let mut factory = deploy_factory(&env);
+    let mut reports = Vec::new();
+
+    let launch = &scenario.launch;
+    let trading_starts_at = launch.trading_delay.map(|delay| env.block_time() + delay);
+    env.set_caller(env.get_account(launch.creator));
+    let events_before = env.events_count(&factory);
+    let result = factory
+        .with_tokens(U512::from(launch.initial_buy.as_u128()))
+        .try_create_token(
+            "Simulated".to_string(),
+            "SIM".to_string(),
+            String::new(),
+            Some(launch.initial_buy).filter(|amount| !amount.is_zero()),
+            None::<LaunchConfig>,
+            launch.creator_lock_duration,
+            trading_starts_at,
+        );
+    reports.push(report(&env, 0, "create_token", result.err(), None, None));
+    let mint = match event_since::<TokenCreated>(&env, &factory, events_before) {
+        Some(event) => event.mint,
+        None => return reports,
+    };
+
+    for (index, step) in scenario.steps.iter().enumerate() {
+        let step_number = index + 1;
+        let entry = match step {
+            Step::Advance(seconds) => {
+                env.advance_block_time(*seconds);
+                report(&env, step_number, "advance", None, None, None)
+            }
+            Step::Buy {
+                account,
+                cspr,
+                min_out,
+            } => {
+                env.set_caller(env.get_account(*account));
+                let events_before = env.events_count(&factory);
+                let result = factory
+                    .with_tokens(U512::from(cspr.as_u128()))
+                    .try_buy(mint, *min_out, None);
+                let bought = result
+                    .is_ok()
+                    .then(|| event_since::<TokenBought>(&env, &factory, events_before))
+                    .flatten();
+                report(
+                    &env,
+                    step_number,
+                    "buy",
+                    result.err(),
+                    bought.as_ref().map(|event| event.tokens_out),
+                    bought.as_ref().map(|event| event.fee_bps),
+                )
+            }
+            Step::Sell {
+                account,
+                tokens,
+                min_out,
+            } => {
+                env.set_caller(env.get_account(*account));
+                let events_before = env.events_count(&factory);
+                let result = factory.try_sell(mint, *tokens, *min_out, None);
+                let sold = result
+                    .is_ok()
+                    .then(|| event_since::<TokenSold>(&env, &factory, events_before))
+                    .flatten();
+                report(
+                    &env,
+                    step_number,
+                    "sell",
+                    result.err(),
+                    sold.as_ref().map(|event| event.cspr_out),
+                    sold.as_ref().map(|event| event.fee_bps),
+                )
+            }
+        };
+        reports.push(entry);
+    }
+    reports
+}
+
+/// Contract error name of a reverted call (falls back to the raw error)
+/// Contract checks revert with their `Error` code, so the name is recoverable
+pub fn error_name(error: &OdraError) -> String {
+    let position = contract_error::all()
+        .into_iter()
+        .position(|contract_error| OdraError::from(contract_error) == *error);
+    if let Some(contract_error) =
+        position.and_then(|index| contract_error::from_code(index as u16 + 1))
+    {
+        return format!("{:?}", contract_error);
+    }
+    if OdraError::from(SecurityError::ReentrantCall) == *error {
+        return "ReentrantCall".to_string();
+    }
+    format!("{:?}", error)
+}
+
+fn deploy_factory(env: &HostEnv) -> TokenFactoryHostRef {
+    env.set_caller(env.get_account(0));
+    TokenFactory::deploy(
+        env,
+        TokenFactoryInitArgs {
+            platform_wallet: env.get_account(0),
+            stories_pool: env.get_account(1),
+            screener_token: env.get_account(2),
+            burn_address: Some(env.get_account(9)),
+        },
+    )
+}
+
+fn report(
+    env: &HostEnv,
+    step: usize,
+    action: &str,
+    error: Option<OdraError>,
+    amount_out: Option<U256>,
+    fee_bps: Option<u32>,
+) -> StepReport {
+    StepReport {
+        step,
+        action: action.to_string(),
+        block_time: env.block_time(),
+        error: error.as_ref().map(error_name),
+        amount_out,
+        fee_bps,
+    }
+}
+
+/// First event of type `T` emitted by the call that started at `events_before` events
+fn event_since<T: EventInstance + FromBytes>(
+    env: &HostEnv,
+    factory: &TokenFactoryHostRef,
+    events_before: u32,
+) -> Option<T> {
+    let events_after = env.events_count(factory);
+    (events_before..events_after)
+        .find_map(|index| env.get_event::<T, _>(factory, index as i32).ok())
+}
+
+/// CSPR motes as a decimal string; attached values must fit the contract's u128 reads
+mod motes {
+    use casper_types::U256;
+    use serde::Deserializer;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+        let value = super::decimal::deserialize(deserializer)?;
+        if value > U256::from(u128::MAX) {
+            return Err(serde::de::Error::custom(format!(
+                "amount exceeds u128 motes: {}",
+                value
+            )));
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulation_reports_failures() {
+        let scenario: Scenario = serde_json::from_str(
+            r#"{
+                "launch": { "creator": 3 },
+                "steps": [
+                    { "buy": { "account": 4, "cspr": "10000000000" } },
+                    { "advance": 301 },
+                    { "buy": { "account": 5, "cspr": "10000000000", "min_out": "1000000000000000000000" } },
+                    { "sell": { "account": 4, "tokens": "0" } },
+                    { "buy": { "account": 5, "cspr": "10000000000" } }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let reports = run(&scenario);
+
+        assert_eq!(reports.len(), 6);
+        assert_eq!(reports[0].error, None);
+        assert_eq!(reports[1].error, None);
+        assert!(reports[1].amount_out.unwrap() > U256::zero());
+        assert_eq!(reports[2].action, "advance");
+        assert_eq!(reports[3].error, Some("SlippageExceeded".to_string()));
+        assert_eq!(reports[4].error, Some("InsufficientTokens".to_string()));
+
+        // Reverted steps leave the VM clean for the next one
+        assert_eq!(reports[5].error, None);
+        assert!(reports[5].amount_out.unwrap() > U256::zero());
+    }
+
+    #[test]
+    fn test_scenario_rejects_amounts_over_u128() {
+        let too_large = U256::from(u128::MAX) + U256::one();
+        let json = format!(
+            r#"{{ "steps": [{{ "buy": {{ "account": 4, "cspr": "{}" }} }}] }}"#,
+            too_large
+        );
+        let error = serde_json::from_str::<Scenario>(&json).unwrap_err();
+        assert!(error.to_string().contains("amount exceeds u128 motes"));
+    }
+}
@@ -0,0 +1,68 @@
+//! `screener-indexer [--cspr-cloud] [--start-at <stream>=<sequence>] <db.sqlite> [records.jsonl]`
+//!
+//! Indexes JSON-line event records from a file, or from stdin when no file is
+//! given (e.g. piped from a stream consumer). With `--cspr-cloud` the lines are
+//! CSPR.cloud `contract-events` streaming messages instead. `--start-at` begins a
+//! new stream at a later sequence than 0. Safe to re-run: records already behind
+//! the cursor are skipped.
+
+use screener_indexer::{Indexer, Store};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+
+const USAGE: &str =
+    "usage: screener-indexer [--cspr-cloud] [--start-at <stream>=<sequence>] <db.sqlite> [records.jsonl]";
+
+fn main() {
+    let mut args = std::env::args().skip(1).peekable();
+    let mut cspr_cloud = false;
+    let mut start_at = None;
+    while let Some(flag) = args.next_if(|arg| arg.starts_with("--")) {
+        match flag.as_str() {
+            "--cspr-cloud" => cspr_cloud = true,
+            "--start-at" => match args.next().as_deref().and_then(parse_start) {
+                Some(start) => start_at = Some(start),
+                None => usage(),
+            },
+            _ => usage(),
+        }
+    }
+    let Some(db) = args.next() else {
+        usage();
+    };
+
+    let result = Store::open(&db).and_then(|store| {
+        let mut indexer = Indexer::new(store);
+        if let Some((stream, sequence)) = &start_at {
+            indexer.start_at(stream, *sequence)?;
+        }
+        let reader: Box<dyn BufRead> = match args.next() {
+            Some(path) => Box::new(BufReader::new(File::open(path)?)),
+            None => Box::new(io::stdin().lock()),
+        };
+        if cspr_cloud {
+            indexer.ingest_cspr_cloud_lines(reader)
+        } else {
+            indexer.ingest_lines(reader)
+        }
+    });
+
+    match result {
+        Ok(stats) => println!("applied {} events, skipped {}", stats.applied, stats.skipped),
+        Err(error) => {
+            eprintln!("error: {}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `<stream>=<sequence>`
+fn parse_start(value: &str) -> Option<(String, u64)> {
+    let (stream, sequence) = value.rsplit_once('=')?;
+    Some((stream.to_string(), sequence.parse().ok()?))
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    std::process::exit(2);
+}
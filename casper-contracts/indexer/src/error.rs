@@ -0,0 +1,64 @@
+use odra::casper_types::bytesrepr;
+use std::fmt;
+
+/// Errors raised while decoding or persisting events
+#[derive(Debug)]
+pub enum IndexerError {
+    /// Event bytes did not decode as the named event
+    Decode(bytesrepr::Error),
+    /// Malformed record line
+    Record(serde_json::Error),
+    /// Malformed hex payload
+    InvalidHex(String),
+    /// Streaming message without a field the record needs
+    MissingField(&'static str),
+    /// Record skips ahead of the stream cursor (missed events)
+    Gap { expected: u64, got: u64 },
+    /// Value does not fit an SQLite integer
+    OutOfRange(u64),
+    Sql(rusqlite::Error),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for IndexerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexerError::Decode(error) => write!(f, "event decoding failed: {}", error),
+            IndexerError::Record(error) => write!(f, "invalid record: {}", error),
+            IndexerError::InvalidHex(value) => write!(f, "invalid hex: {}", value),
+            IndexerError::MissingField(field) => write!(f, "message has no {}", field),
+            IndexerError::Gap { expected, got } => {
+                write!(f, "sequence gap: expected {}, got {}", expected, got)
+            }
+            IndexerError::OutOfRange(value) => write!(f, "value out of range: {}", value),
+            IndexerError::Sql(error) => write!(f, "sqlite error: {}", error),
+            IndexerError::Io(error) => write!(f, "io error: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for IndexerError {}
+
+impl From<bytesrepr::Error> for IndexerError {
+    fn from(error: bytesrepr::Error) -> Self {
+        IndexerError::Decode(error)
+    }
+}
+
+impl From<serde_json::Error> for IndexerError {
+    fn from(error: serde_json::Error) -> Self {
+        IndexerError::Record(error)
+    }
+}
+
+impl From<rusqlite::Error> for IndexerError {
+    fn from(error: rusqlite::Error) -> Self {
+        IndexerError::Sql(error)
+    }
+}
+
+impl From<std::io::Error> for IndexerError {
+    fn from(error: std::io::Error) -> Self {
+        IndexerError::Io(error)
+    }
+}
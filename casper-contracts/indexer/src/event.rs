@@ -0,0 +1,66 @@
+use crate::error::IndexerError;
+use odra::casper_types::bytesrepr::{self, FromBytes};
+use screener_contracts::token_factory::{
+    CTOExecuted, CreatorFeesClaimed, PoolDepositClaimed, TokenBought, TokenCreated, TokenGraduated,
+    TokenInfoUpdated, TokenSold,
+};
+
+/// Prefix written before the event name in CES event bytes
+const EVENT_PREFIX: &str = "event_";
+
+/// Factory events the indexer persists; anything else is kept as `Other(name)`
+#[derive(Clone, Debug, PartialEq)]
+pub enum FactoryEvent {
+    TokenCreated(TokenCreated),
+    TokenBought(TokenBought),
+    TokenSold(TokenSold),
+    TokenGraduated(TokenGraduated),
+    CreatorFeesClaimed(CreatorFeesClaimed),
+    TokenInfoUpdated(TokenInfoUpdated),
+    CTOExecuted(CTOExecuted),
+    PoolDepositClaimed(PoolDepositClaimed),
+    Other(String),
+}
+
+impl FactoryEvent {
+    pub fn name(&self) -> &str {
+        match self {
+            FactoryEvent::TokenCreated(_) => "TokenCreated",
+            FactoryEvent::TokenBought(_) => "TokenBought",
+            FactoryEvent::TokenSold(_) => "TokenSold",
+            FactoryEvent::TokenGraduated(_) => "TokenGraduated",
+            FactoryEvent::CreatorFeesClaimed(_) => "CreatorFeesClaimed",
+            FactoryEvent::TokenInfoUpdated(_) => "TokenInfoUpdated",
+            FactoryEvent::CTOExecuted(_) => "CTOExecuted",
+            FactoryEvent::PoolDepositClaimed(_) => "PoolDepositClaimed",
+            FactoryEvent::Other(name) => name,
+        }
+    }
+}
+
+/// Event name (without the `event_` prefix) read from the head of CES bytes
+pub fn event_name(bytes: &[u8]) -> Result<String, IndexerError> {
+    let (name, _) = String::from_bytes(bytes)?;
+    Ok(name.strip_prefix(EVENT_PREFIX).unwrap_or(&name).to_string())
+}
+
+/// Decode CES event bytes (name, then fields) into a factory event
+pub fn decode_event(bytes: &[u8]) -> Result<FactoryEvent, IndexerError> {
+    let name = event_name(bytes)?;
+    let event = match name.as_str() {
+        "TokenCreated" => FactoryEvent::TokenCreated(decode(bytes)?),
+        "TokenBought" => FactoryEvent::TokenBought(decode(bytes)?),
+        "TokenSold" => FactoryEvent::TokenSold(decode(bytes)?),
+        "TokenGraduated" => FactoryEvent::TokenGraduated(decode(bytes)?),
+        "CreatorFeesClaimed" => FactoryEvent::CreatorFeesClaimed(decode(bytes)?),
+        "TokenInfoUpdated" => FactoryEvent::TokenInfoUpdated(decode(bytes)?),
+        "CTOExecuted" => FactoryEvent::CTOExecuted(decode(bytes)?),
+        "PoolDepositClaimed" => FactoryEvent::PoolDepositClaimed(decode(bytes)?),
+        _ => FactoryEvent::Other(name),
+    };
+    Ok(event)
+}
+
+fn decode<T: FromBytes>(bytes: &[u8]) -> Result<T, IndexerError> {
+    Ok(bytesrepr::deserialize_from_slice(bytes)?)
+}
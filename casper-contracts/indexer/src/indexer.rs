@@ -0,0 +1,337 @@
+use crate::error::IndexerError;
+use crate::event::decode_event;
+use crate::record::EventRecord;
+use crate::store::Store;
+use std::io::BufRead;
+
+/// Counts of one ingestion run
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IngestStats {
+    pub applied: u64,
+    /// Records at or below the stream cursor (already indexed)
+    pub skipped: u64,
+}
+
+/// Replays event records into the store, resuming from each stream's cursor
+pub struct Indexer {
+    store: Store,
+}
+
+impl Indexer {
+    pub fn new(store: Store) -> Self {
+        Self { store }
+    }
+
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    /// Start `stream` at `sequence` instead of 0 (a factory joined after its first events)
+    pub fn start_at(&mut self, stream: &str, sequence: u64) -> Result<(), IndexerError> {
+        self.store.start_at(stream, sequence)
+    }
+
+    /// Index one record; returns false when it was already indexed
+    /// Records must follow the cursor without gaps (a new stream starts at 0, or at `start_at`)
+    pub fn ingest(&mut self, record: &EventRecord) -> Result<bool, IndexerError> {
+        let expected = match self.store.cursor(&record.stream)? {
+            Some(cursor) => cursor + 1,
+            None => 0,
+        };
+        if record.sequence < expected {
+            return Ok(false);
+        }
+        if record.sequence != expected {
+            return Err(IndexerError::Gap { expected, got: record.sequence });
+        }
+        let event = decode_event(&record.event_bytes()?)?;
+        self.store.apply(record, &event)?;
+        Ok(true)
+    }
+
+    /// Index JSON-line records (a captured file or a live stream on stdin)
+    /// Stops at the first bad record; the cursor keeps everything before it
+    pub fn ingest_lines<R: BufRead>(&mut self, reader: R) -> Result<IngestStats, IndexerError> {
+        self.ingest_lines_with(reader, EventRecord::from_json)
+    }
+
+    /// Index CSPR.cloud streaming messages, one per line (`Ping` keep-alives are skipped)
+    pub fn ingest_cspr_cloud_lines<R: BufRead>(&mut self, reader: R) -> Result<IngestStats, IndexerError> {
+        self.ingest_lines_with(reader, EventRecord::from_cspr_cloud)
+    }
+
+    fn ingest_lines_with<R: BufRead>(
+        &mut self,
+        reader: R,
+        parse: fn(&str) -> Result<EventRecord, IndexerError>,
+    ) -> Result<IngestStats, IndexerError> {
+        let mut stats = IngestStats::default();
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line == "Ping" {
+                continue;
+            }
+            if self.ingest(&parse(line)?)? {
+                stats.applied += 1;
+            } else {
+                stats.skipped += 1;
+            }
+        }
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::FactoryEvent;
+    use screener_client::decode::encode_hex;
+    use odra::casper_types::{U256, U512};
+    use odra::host::{Deployer, HostEnv, HostRef};
+    use odra::casper_types::bytesrepr::ToBytes;
+    use screener_contracts::token_factory::{
+        PoolDepositClaimed, TokenCreated, TokenFactory, TokenFactoryHostRef, TokenFactoryInitArgs,
+    };
+
+    const STREAM: &str = "factory";
+
+    fn deploy_factory(env: &HostEnv) -> TokenFactoryHostRef {
+        env.set_caller(env.get_account(0));
+        TokenFactory::deploy(
+            env,
+            TokenFactoryInitArgs {
+                platform_wallet: env.get_account(0),
+                stories_pool: env.get_account(1),
+                screener_token: env.get_account(2),
+                burn_address: Some(env.get_account(9)),
+            },
+        )
+    }
+
+    /// Capture every factory event from `from` on as JSON-line records
+    fn capture(env: &HostEnv, factory: &TokenFactoryHostRef, from: u32) -> String {
+        let mut lines = String::new();
+        let mut index = from;
+        while let Ok(bytes) = env.get_event_bytes(factory, index as i32) {
+            let record = EventRecord {
+                stream: STREAM.to_string(),
+                sequence: index as u64,
+                bytes: encode_hex(&bytes),
+                deploy_hash: None,
+                block_height: None,
+                timestamp: None,
+            };
+            lines.push_str(&record.to_json().unwrap());
+            lines.push('\n');
+            index += 1;
+        }
+        lines
+    }
+
+    /// Launch a token, buy, sell, update info and claim fees
+    fn run_launch(env: &HostEnv, factory: &mut TokenFactoryHostRef) -> TokenCreated {
+        let creator = env.get_account(3);
+        env.set_caller(creator);
+        factory.create_token(
+            "Test".to_string(),
+            "TEST".to_string(),
+            "https://test.com".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        let created: TokenCreated = env.get_event(&*factory, -1).unwrap();
+
+        env.set_caller(env.get_account(4));
+        factory
+            .with_tokens(U512::from(10_000_000_000u64))
+            .buy(created.mint, U256::zero(), None);
+        factory.sell(created.mint, U256::from(1_000_000_000u64), U256::zero(), None);
+
+        env.set_caller(creator);
+        factory.update_token_info(created.mint, Some("https://site".to_string()), None, None, None);
+        factory.claim_creator_fees(created.mint);
+        created
+    }
+
+    fn count(indexer: &Indexer, sql: &str) -> i64 {
+        indexer.store().connection().query_row(sql, [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_decode_contract_events() {
+        let env = odra_test::env();
+        let mut factory = deploy_factory(&env);
+        let created = run_launch(&env, &mut factory);
+
+        let mut events = Vec::new();
+        while let Ok(bytes) = env.get_event_bytes(&factory, events.len() as i32) {
+            events.push(decode_event(&bytes).unwrap());
+        }
+        assert!(events.contains(&FactoryEvent::TokenCreated(created)));
+        assert!(events.iter().any(|event| event.name() == "TokenBought"));
+        assert!(events.iter().any(|event| event.name() == "CreatorFeesClaimed"));
+
+        // Events outside the indexed set are still named
+        assert_eq!(events[0], FactoryEvent::Other("FactoryInitialized".to_string()));
+    }
+
+    #[test]
+    fn test_pool_deposit_claims_are_stored() {
+        let env = odra_test::env();
+        let claimed = PoolDepositClaimed {
+            token_contract: env.get_account(5),
+            creator: env.get_account(4),
+            amount: U256::from(100_000_000_000u64),
+        };
+        let record = EventRecord {
+            stream: STREAM.to_string(),
+            sequence: 0,
+            bytes: encode_hex(&claimed.to_bytes().unwrap()),
+            deploy_hash: None,
+            block_height: None,
+            timestamp: None,
+        };
+        assert_eq!(
+            decode_event(&record.event_bytes().unwrap()).unwrap(),
+            FactoryEvent::PoolDepositClaimed(claimed)
+        );
+
+        let mut indexer = Indexer::new(Store::open_in_memory().unwrap());
+        indexer.ingest(&record).unwrap();
+        assert_eq!(count(&indexer, "SELECT COUNT(*) FROM pool_deposit_claims"), 1);
+    }
+
+    #[test]
+    fn test_replay_fixture_into_sqlite() {
+        let env = odra_test::env();
+        let mut factory = deploy_factory(&env);
+        let created = run_launch(&env, &mut factory);
+        let fixture = capture(&env, &factory, 0);
+
+        let mut indexer = Indexer::new(Store::open_in_memory().unwrap());
+        let stats = indexer.ingest_lines(fixture.as_bytes()).unwrap();
+        let total = fixture.lines().count() as u64;
+        assert_eq!(stats, IngestStats { applied: total, skipped: 0 });
+
+        assert_eq!(count(&indexer, "SELECT COUNT(*) FROM tokens"), 1);
+        assert_eq!(count(&indexer, "SELECT COUNT(*) FROM trades WHERE side = 'buy'"), 1);
+        assert_eq!(count(&indexer, "SELECT COUNT(*) FROM trades WHERE side = 'sell'"), 1);
+        assert_eq!(count(&indexer, "SELECT COUNT(*) FROM fee_claims"), 1);
+        let website: String = indexer
+            .store()
+            .connection()
+            .query_row(
+                "SELECT website FROM tokens WHERE mint = ?1",
+                [created.mint.to_formatted_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(website, "https://site");
+        assert_eq!(indexer.store().cursor(STREAM).unwrap(), Some(total - 1));
+    }
+
+    #[test]
+    fn test_resume_from_cursor() {
+        let env = odra_test::env();
+        let mut factory = deploy_factory(&env);
+        run_launch(&env, &mut factory);
+        let first = capture(&env, &factory, 0);
+        let first_count = first.lines().count() as u64;
+
+        let path = std::env::temp_dir().join(format!("screener-indexer-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        {
+            let mut indexer = Indexer::new(Store::open(&path).unwrap());
+            indexer.ingest_lines(first.as_bytes()).unwrap();
+        }
+
+        // New events after a restart: the old ones are skipped
+        env.advance_block_time(1);
+        run_launch(&env, &mut factory);
+        let all = capture(&env, &factory, 0);
+        let mut indexer = Indexer::new(Store::open(&path).unwrap());
+        let stats = indexer.ingest_lines(all.as_bytes()).unwrap();
+        assert_eq!(stats.skipped, first_count);
+        assert_eq!(stats.applied, all.lines().count() as u64 - first_count);
+        assert_eq!(count(&indexer, "SELECT COUNT(*) FROM tokens"), 2);
+        assert_eq!(count(&indexer, "SELECT COUNT(*) FROM trades"), 4);
+
+        // A bad record stops ingestion without moving the cursor
+        let cursor = indexer.store().cursor(STREAM).unwrap();
+        let bad = format!(
+            "{{\"stream\":\"{}\",\"sequence\":{},\"bytes\":\"zz\"}}\n",
+            STREAM,
+            cursor.unwrap() + 1
+        );
+        assert!(indexer.ingest_lines(bad.as_bytes()).is_err());
+        assert_eq!(indexer.store().cursor(STREAM).unwrap(), cursor);
+
+        drop(indexer);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_gap_stops_ingestion() {
+        let env = odra_test::env();
+        let mut factory = deploy_factory(&env);
+        run_launch(&env, &mut factory);
+        let records: Vec<EventRecord> = capture(&env, &factory, 0)
+            .lines()
+            .map(|line| EventRecord::from_json(line).unwrap())
+            .collect();
+
+        // A new stream starts at 0 unless told otherwise
+        let mut indexer = Indexer::new(Store::open_in_memory().unwrap());
+        assert!(matches!(
+            indexer.ingest(&records[1]),
+            Err(IndexerError::Gap { expected: 0, got: 1 })
+        ));
+        assert_eq!(indexer.store().cursor(STREAM).unwrap(), None);
+
+        // Skipping a record is refused without moving the cursor
+        indexer.ingest(&records[0]).unwrap();
+        assert!(matches!(
+            indexer.ingest(&records[2]),
+            Err(IndexerError::Gap { expected: 1, got: 2 })
+        ));
+        assert_eq!(indexer.store().cursor(STREAM).unwrap(), Some(0));
+
+        // Records beyond the SQLite integer range are rejected
+        let mut record = records[1].clone();
+        record.stream = "other".to_string();
+        record.sequence = 0;
+        record.block_height = Some(u64::MAX);
+        assert!(matches!(indexer.ingest(&record), Err(IndexerError::OutOfRange(u64::MAX))));
+    }
+
+    #[test]
+    fn test_stream_starts_at_sequence() {
+        let env = odra_test::env();
+        let mut factory = deploy_factory(&env);
+        run_launch(&env, &mut factory);
+        let records: Vec<EventRecord> = capture(&env, &factory, 0)
+            .lines()
+            .map(|line| EventRecord::from_json(line).unwrap())
+            .collect();
+
+        // Joined at 2: earlier records count as indexed
+        let mut indexer = Indexer::new(Store::open_in_memory().unwrap());
+        indexer.start_at(STREAM, 2).unwrap();
+        assert_eq!(indexer.store().cursor(STREAM).unwrap(), Some(1));
+        assert!(!indexer.ingest(&records[1]).unwrap());
+        assert!(indexer.ingest(&records[2]).unwrap());
+        assert_eq!(count(&indexer, "SELECT COUNT(*) FROM events"), 1);
+
+        // Re-running with the same start is a no-op; skipping indexed events is refused
+        indexer.start_at(STREAM, 2).unwrap();
+        assert_eq!(indexer.store().cursor(STREAM).unwrap(), Some(2));
+        assert!(matches!(
+            indexer.start_at(STREAM, 5),
+            Err(IndexerError::Gap { expected: 3, got: 5 })
+        ));
+        assert_eq!(indexer.store().cursor(STREAM).unwrap(), Some(2));
+    }
+}
@@ -0,0 +1,18 @@
+//! Indexer for `TokenFactory` events
+//!
+//! Decodes the contract's CES events (`event_<Name>` string, then the fields)
+//! and stores them in SQLite. Records arrive as JSON lines carrying the event
+//! bytes and the factory's event index; a per-stream cursor makes replays and
+//! restarts idempotent, so captured files can be replayed with no network.
+
+pub mod error;
+pub mod event;
+pub mod indexer;
+pub mod record;
+pub mod store;
+
+pub use error::IndexerError;
+pub use event::{decode_event, FactoryEvent};
+pub use indexer::{IngestStats, Indexer};
+pub use record::EventRecord;
+pub use store::Store;
@@ -0,0 +1,81 @@
+use crate::error::IndexerError;
+use screener_client::decode::parse_hex;
+use serde::{Deserialize, Serialize};
+
+/// One captured contract event, as a JSON line
+///
+/// `sequence` is the CES event index of the factory (monotonic per `stream`),
+/// which is what the resumable cursor tracks.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EventRecord {
+    /// Source of the events, e.g. the factory package hash
+    pub stream: String,
+    pub sequence: u64,
+    /// Hex-encoded CES event bytes
+    pub bytes: String,
+    #[serde(default)]
+    pub deploy_hash: Option<String>,
+    #[serde(default)]
+    pub block_height: Option<u64>,
+    #[serde(default)]
+    pub timestamp: Option<String>,
+}
+
+impl EventRecord {
+    pub fn from_json(line: &str) -> Result<Self, IndexerError> {
+        Ok(serde_json::from_str(line)?)
+    }
+
+    pub fn to_json(&self) -> Result<String, IndexerError> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Record from one CSPR.cloud `contract-events` streaming message
+    ///
+    /// `data.raw_data` (the CES bytes) becomes `bytes` and `extra.event_id` the
+    /// sequence; the stream is `hash-<data.contract_package_hash>`. The decoded
+    /// `data.data` fields are ignored.
+    pub fn from_cspr_cloud(message: &str) -> Result<Self, IndexerError> {
+        let message: StreamMessage = serde_json::from_str(message)?;
+        Ok(Self {
+            stream: format!("hash-{}", message.data.contract_package_hash),
+            sequence: message.extra.event_id.ok_or(IndexerError::MissingField("extra.event_id"))?,
+            bytes: message.data.raw_data.ok_or(IndexerError::MissingField("data.raw_data"))?,
+            deploy_hash: message.extra.deploy_hash,
+            block_height: message.extra.block_height,
+            timestamp: message.timestamp,
+        })
+    }
+
+    /// Decoded event bytes
+    pub fn event_bytes(&self) -> Result<Vec<u8>, IndexerError> {
+        parse_hex(&self.bytes).map_err(|_| IndexerError::InvalidHex(self.bytes.clone()))
+    }
+}
+
+/// The parts of a CSPR.cloud streaming message the indexer reads
+#[derive(Deserialize)]
+struct StreamMessage {
+    data: StreamEvent,
+    #[serde(default)]
+    extra: StreamExtra,
+    #[serde(default)]
+    timestamp: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct StreamEvent {
+    contract_package_hash: String,
+    #[serde(default)]
+    raw_data: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+struct StreamExtra {
+    #[serde(default)]
+    event_id: Option<u64>,
+    #[serde(default)]
+    deploy_hash: Option<String>,
+    #[serde(default)]
+    block_height: Option<u64>,
+}
@@ -0,0 +1,316 @@
+use crate::error::IndexerError;
+use crate::event::FactoryEvent;
+use crate::record::EventRecord;
+use odra::prelude::Address;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use std::path::Path;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS cursors (
+    stream TEXT PRIMARY KEY,
+    sequence INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS events (
+    stream TEXT NOT NULL,
+    sequence INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    deploy_hash TEXT,
+    block_height INTEGER,
+    timestamp TEXT,
+    PRIMARY KEY (stream, sequence)
+);
+CREATE TABLE IF NOT EXISTS tokens (
+    mint TEXT PRIMARY KEY,
+    creator TEXT NOT NULL,
+    name TEXT NOT NULL,
+    symbol TEXT NOT NULL,
+    initial_buy_cspr TEXT NOT NULL,
+    total_supply TEXT NOT NULL,
+    decimals INTEGER NOT NULL,
+    virtual_cspr_reserves TEXT NOT NULL,
+    virtual_token_reserves TEXT NOT NULL,
+    graduation_target TEXT NOT NULL,
+    website TEXT,
+    telegram TEXT,
+    twitter TEXT,
+    banner_uri TEXT,
+    graduated INTEGER NOT NULL DEFAULT 0,
+    final_mcap_cspr TEXT,
+    dex_address TEXT,
+    created_deploy_hash TEXT,
+    created_at TEXT
+);
+CREATE TABLE IF NOT EXISTS trades (
+    stream TEXT NOT NULL,
+    sequence INTEGER NOT NULL,
+    mint TEXT NOT NULL,
+    trader TEXT NOT NULL,
+    side TEXT NOT NULL,
+    cspr_amount TEXT NOT NULL,
+    token_amount TEXT NOT NULL,
+    new_price TEXT NOT NULL,
+    fee_bps INTEGER NOT NULL,
+    deploy_hash TEXT,
+    timestamp TEXT,
+    PRIMARY KEY (stream, sequence)
+);
+CREATE INDEX IF NOT EXISTS trades_by_mint ON trades (mint, sequence);
+CREATE TABLE IF NOT EXISTS fee_claims (
+    stream TEXT NOT NULL,
+    sequence INTEGER NOT NULL,
+    mint TEXT NOT NULL,
+    creator TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    PRIMARY KEY (stream, sequence)
+);
+CREATE TABLE IF NOT EXISTS cto_changes (
+    stream TEXT NOT NULL,
+    sequence INTEGER NOT NULL,
+    mint TEXT NOT NULL,
+    old_creator TEXT NOT NULL,
+    new_creator TEXT NOT NULL,
+    price_paid TEXT NOT NULL,
+    PRIMARY KEY (stream, sequence)
+);
+CREATE TABLE IF NOT EXISTS pool_deposit_claims (
+    stream TEXT NOT NULL,
+    sequence INTEGER NOT NULL,
+    token_contract TEXT NOT NULL,
+    creator TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    PRIMARY KEY (stream, sequence)
+);
+";
+
+/// SQLite persistence; amounts are decimal strings, addresses formatted strings
+pub struct Store {
+    connection: Connection,
+}
+
+impl Store {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, IndexerError> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, IndexerError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, IndexerError> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Last sequence stored for `stream`
+    pub fn cursor(&self, stream: &str) -> Result<Option<u64>, IndexerError> {
+        let sequence: Option<i64> = self
+            .connection
+            .query_row(
+                "SELECT sequence FROM cursors WHERE stream = ?1",
+                params![stream],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(sequence.map(|sequence| sequence as u64))
+    }
+
+    /// Begin `stream` at `sequence`, counting earlier events as indexed
+    /// No-op once the cursor reaches `sequence`; refuses to skip past indexed events
+    pub fn start_at(&mut self, stream: &str, sequence: u64) -> Result<(), IndexerError> {
+        let expected = match self.cursor(stream)? {
+            Some(cursor) => cursor + 1,
+            None => 0,
+        };
+        if sequence <= expected {
+            return Ok(());
+        }
+        if expected > 0 {
+            return Err(IndexerError::Gap { expected, got: sequence });
+        }
+        self.connection.execute(
+            "INSERT INTO cursors (stream, sequence) VALUES (?1, ?2)",
+            params![stream, to_sql_integer(sequence - 1)?],
+        )?;
+        Ok(())
+    }
+
+    /// Store one event and advance the cursor atomically
+    pub fn apply(&mut self, record: &EventRecord, event: &FactoryEvent) -> Result<(), IndexerError> {
+        let sequence = to_sql_integer(record.sequence)?;
+        let block_height = record.block_height.map(to_sql_integer).transpose()?;
+        let tx = self.connection.transaction()?;
+        tx.execute(
+            "INSERT INTO events (stream, sequence, name, deploy_hash, block_height, timestamp)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                record.stream,
+                sequence,
+                event.name(),
+                record.deploy_hash,
+                block_height,
+                record.timestamp,
+            ],
+        )?;
+        apply_event(&tx, record, sequence, event)?;
+        tx.execute(
+            "INSERT INTO cursors (stream, sequence) VALUES (?1, ?2)
+             ON CONFLICT (stream) DO UPDATE SET sequence = excluded.sequence",
+            params![record.stream, sequence],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+fn apply_event(
+    tx: &Transaction,
+    record: &EventRecord,
+    sequence: i64,
+    event: &FactoryEvent,
+) -> Result<(), IndexerError> {
+    match event {
+        FactoryEvent::TokenCreated(event) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO tokens (mint, creator, name, symbol, initial_buy_cspr, total_supply,
+                     decimals, virtual_cspr_reserves, virtual_token_reserves, graduation_target,
+                     created_deploy_hash, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    address(&event.mint),
+                    address(&event.creator),
+                    event.name,
+                    event.symbol,
+                    event.initial_buy_cspr.to_string(),
+                    event.total_supply.to_string(),
+                    event.decimals,
+                    event.virtual_cspr_reserves.to_string(),
+                    event.virtual_token_reserves.to_string(),
+                    event.graduation_target.to_string(),
+                    record.deploy_hash,
+                    record.timestamp,
+                ],
+            )?;
+        }
+        FactoryEvent::TokenBought(event) => {
+            tx.execute(
+                "INSERT INTO trades (stream, sequence, mint, trader, side, cspr_amount, token_amount,
+                     new_price, fee_bps, deploy_hash, timestamp)
+                 VALUES (?1, ?2, ?3, ?4, 'buy', ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    record.stream,
+                    sequence,
+                    address(&event.mint),
+                    address(&event.buyer),
+                    event.cspr_amount.to_string(),
+                    event.tokens_out.to_string(),
+                    event.new_price.to_string(),
+                    event.fee_bps,
+                    record.deploy_hash,
+                    record.timestamp,
+                ],
+            )?;
+        }
+        FactoryEvent::TokenSold(event) => {
+            tx.execute(
+                "INSERT INTO trades (stream, sequence, mint, trader, side, cspr_amount, token_amount,
+                     new_price, fee_bps, deploy_hash, timestamp)
+                 VALUES (?1, ?2, ?3, ?4, 'sell', ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    record.stream,
+                    sequence,
+                    address(&event.mint),
+                    address(&event.seller),
+                    event.cspr_out.to_string(),
+                    event.tokens_in.to_string(),
+                    event.new_price.to_string(),
+                    event.fee_bps,
+                    record.deploy_hash,
+                    record.timestamp,
+                ],
+            )?;
+        }
+        FactoryEvent::TokenGraduated(event) => {
+            tx.execute(
+                "UPDATE tokens SET graduated = 1, final_mcap_cspr = ?2, dex_address = ?3 WHERE mint = ?1",
+                params![
+                    address(&event.mint),
+                    event.final_mcap_cspr.to_string(),
+                    event.dex_address.as_ref().map(address),
+                ],
+            )?;
+        }
+        FactoryEvent::CreatorFeesClaimed(event) => {
+            tx.execute(
+                "INSERT INTO fee_claims (stream, sequence, mint, creator, amount) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    record.stream,
+                    sequence,
+                    address(&event.mint),
+                    address(&event.creator),
+                    event.amount.to_string(),
+                ],
+            )?;
+        }
+        FactoryEvent::TokenInfoUpdated(event) => {
+            tx.execute(
+                // Fields left as None are unchanged on-chain
+                "UPDATE tokens SET website = COALESCE(?2, website), telegram = COALESCE(?3, telegram),
+                     twitter = COALESCE(?4, twitter), banner_uri = COALESCE(?5, banner_uri)
+                 WHERE mint = ?1",
+                params![
+                    address(&event.mint),
+                    event.website,
+                    event.telegram,
+                    event.twitter,
+                    event.banner_uri,
+                ],
+            )?;
+        }
+        FactoryEvent::CTOExecuted(event) => {
+            tx.execute(
+                "INSERT INTO cto_changes (stream, sequence, mint, old_creator, new_creator, price_paid)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    record.stream,
+                    sequence,
+                    address(&event.mint),
+                    address(&event.old_creator),
+                    address(&event.new_creator),
+                    event.price_paid.to_string(),
+                ],
+            )?;
+            tx.execute(
+                "UPDATE tokens SET creator = ?2 WHERE mint = ?1",
+                params![address(&event.mint), address(&event.new_creator)],
+            )?;
+        }
+        FactoryEvent::PoolDepositClaimed(event) => {
+            tx.execute(
+                "INSERT INTO pool_deposit_claims (stream, sequence, token_contract, creator, amount)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    record.stream,
+                    sequence,
+                    address(&event.token_contract),
+                    address(&event.creator),
+                    event.amount.to_string(),
+                ],
+            )?;
+        }
+        // Only listed in `events`
+        FactoryEvent::Other(_) => {}
+    }
+    Ok(())
+}
+
+fn to_sql_integer(value: u64) -> Result<i64, IndexerError> {
+    i64::try_from(value).map_err(|_| IndexerError::OutOfRange(value))
+}
+
+fn address(address: &Address) -> String {
+    address.to_formatted_string()
+}
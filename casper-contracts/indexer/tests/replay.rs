@@ -0,0 +1,59 @@
+//! Replays the recorded fixtures in `tests/fixtures` (no OdraVM, no network)
+//!
+//! `launch.jsonl` holds the factory events of one launch as indexer records:
+//! `FactoryInitialized`, `TokenCreated`, `TradingOpened`, a buy, a sell, an info
+//! update and a creator fee claim. `cspr_cloud_stream.jsonl` carries the same
+//! events as CSPR.cloud streaming messages, with a `Ping` keep-alive in between.
+
+use screener_indexer::{EventRecord, IndexerError, IngestStats, Indexer, Store};
+
+const LAUNCH: &str = include_str!("fixtures/launch.jsonl");
+const CSPR_CLOUD_STREAM: &str = include_str!("fixtures/cspr_cloud_stream.jsonl");
+
+const MINT: &str = "account-hash-dc6f17bbec824fff8f86587966b2047db6ab736785840151f13d1dab124e2a54";
+
+fn query<T: rusqlite::types::FromSql>(indexer: &Indexer, sql: &str) -> T {
+    indexer.store().connection().query_row(sql, [], |row| row.get(0)).unwrap()
+}
+
+#[test]
+fn test_replay_launch_fixture() {
+    let mut indexer = Indexer::new(Store::open_in_memory().unwrap());
+    let stats = indexer.ingest_lines(LAUNCH.as_bytes()).unwrap();
+    assert_eq!(stats, IngestStats { applied: 7, skipped: 0 });
+
+    assert_eq!(query::<String>(&indexer, "SELECT mint FROM tokens"), MINT);
+    assert_eq!(query::<String>(&indexer, "SELECT website FROM tokens"), "https://site");
+    assert_eq!(query::<String>(&indexer, "SELECT cspr_amount FROM trades WHERE side = 'buy'"), "10000000000");
+    assert_eq!(query::<i64>(&indexer, "SELECT COUNT(*) FROM trades WHERE side = 'sell'"), 1);
+    assert_eq!(query::<String>(&indexer, "SELECT amount FROM fee_claims"), "31302145");
+    assert_eq!(query::<String>(&indexer, "SELECT name FROM events WHERE sequence = 2"), "TradingOpened");
+    assert_eq!(query::<i64>(&indexer, "SELECT block_height FROM events WHERE sequence = 6"), 1_500_015);
+
+    // Replaying the same file is a no-op
+    let stats = indexer.ingest_lines(LAUNCH.as_bytes()).unwrap();
+    assert_eq!(stats, IngestStats { applied: 0, skipped: 7 });
+}
+
+#[test]
+fn test_cspr_cloud_messages_map_to_records() {
+    let records: Vec<EventRecord> = LAUNCH.lines().map(|line| EventRecord::from_json(line).unwrap()).collect();
+    let adapted: Vec<EventRecord> = CSPR_CLOUD_STREAM
+        .lines()
+        .filter(|line| *line != "Ping")
+        .map(|line| EventRecord::from_cspr_cloud(line).unwrap())
+        .collect();
+    assert_eq!(adapted, records);
+
+    let mut indexer = Indexer::new(Store::open_in_memory().unwrap());
+    let stats = indexer.ingest_cspr_cloud_lines(CSPR_CLOUD_STREAM.as_bytes()).unwrap();
+    assert_eq!(stats, IngestStats { applied: 7, skipped: 0 });
+    assert_eq!(indexer.store().cursor(&records[0].stream).unwrap(), Some(6));
+
+    // Without the raw CES bytes there is nothing to decode
+    let message = r#"{"action":"emitted","data":{"contract_package_hash":"00","name":"TokenBought"},"extra":{"event_id":7}}"#;
+    assert!(matches!(
+        EventRecord::from_cspr_cloud(message),
+        Err(IndexerError::MissingField("data.raw_data"))
+    ));
+}
@@ -2,7 +2,10 @@
 #![cfg_attr(not(test), no_main)]
 extern crate alloc;
 
+pub mod security;
 pub mod token_factory;
+#[cfg(test)]
+pub mod mock_cep18;
 
 // Re-export for easy access
 pub use token_factory::TokenFactory;
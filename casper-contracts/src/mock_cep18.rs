@@ -0,0 +1,93 @@
+use odra::prelude::*;
+use odra::casper_types::U256;
+use crate::token_factory::TokenFactoryContractRef;
+
+// ============================================================================
+// MOCK CEP-18 (OdraVM tests only)
+// ============================================================================
+
+/// Errors
+#[odra::odra_error]
+#[derive(Debug)]
+pub enum MockError {
+    InsufficientBalance = 1,
+    InsufficientAllowance = 2,
+}
+
+/// Minimal CEP-18 token exposing the entrypoints used by existing-token pools
+#[odra::module]
+pub struct MockCep18 {
+    balances: Mapping<Address, U256>,
+    allowances: Mapping<(Address, Address), U256>,
+    /// Basis points withheld on every transfer (simulates a misbehaving token)
+    transfer_tax_bps: Var<u32>,
+    /// Factory called back on every transfer (simulates a reentrant token)
+    reenter_target: Var<Option<Address>>,
+}
+
+#[odra::module]
+impl MockCep18 {
+    /// Mint `amount` to `owner` (no access control)
+    pub fn mint(&mut self, owner: &Address, amount: &U256) {
+        let balance = self.balances.get_or_default(owner);
+        self.balances.set(owner, balance + *amount);
+    }
+
+    /// Withhold `bps` of every transfer from the recipient
+    pub fn set_transfer_tax_bps(&mut self, bps: u32) {
+        self.transfer_tax_bps.set(bps);
+    }
+
+    /// Call back `claim_referral_fees` on `target` during every transfer
+    pub fn set_reenter_target(&mut self, target: Option<Address>) {
+        self.reenter_target.set(target);
+    }
+
+    pub fn approve(&mut self, spender: &Address, amount: &U256) {
+        let owner = self.env().caller();
+        self.allowances.set(&(owner, *spender), *amount);
+    }
+
+    pub fn transfer(&mut self, recipient: &Address, amount: &U256) {
+        let sender = self.env().caller();
+        self.move_tokens(&sender, recipient, amount);
+    }
+
+    pub fn transfer_from(&mut self, owner: &Address, recipient: &Address, amount: &U256) {
+        let spender = self.env().caller();
+        let key = (*owner, spender);
+        let allowance = self.allowances.get_or_default(&key);
+        if allowance < *amount {
+            self.env().revert(MockError::InsufficientAllowance);
+        }
+        self.allowances.set(&key, allowance - *amount);
+        self.move_tokens(owner, recipient, amount);
+    }
+
+    pub fn balance_of(&self, address: &Address) -> U256 {
+        self.balances.get_or_default(address)
+    }
+
+    pub fn allowance(&self, owner: &Address, spender: &Address) -> U256 {
+        self.allowances.get_or_default(&(*owner, *spender))
+    }
+}
+
+impl MockCep18 {
+    fn move_tokens(&mut self, sender: &Address, recipient: &Address, amount: &U256) {
+        let sender_balance = self.balances.get_or_default(sender);
+        if sender_balance < *amount {
+            self.env().revert(MockError::InsufficientBalance);
+        }
+        self.balances.set(sender, sender_balance - *amount);
+
+        // Taxed amount simply disappears
+        let tax = *amount * U256::from(self.transfer_tax_bps.get_or_default()) / U256::from(10_000u32);
+        let recipient_balance = self.balances.get_or_default(recipient);
+        self.balances.set(recipient, recipient_balance + *amount - tax);
+
+        if let Some(target) = self.reenter_target.get().flatten() {
+            TokenFactoryContractRef::new(self.env(), target).claim_referral_fees();
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use odra::prelude::*;
+
+// ============================================================================
+// REENTRANCY GUARD
+// ============================================================================
+
+/// Errors
+#[odra::odra_error]
+#[derive(Debug)]
+pub enum SecurityError {
+    ReentrantCall = 1_000,
+}
+
+/// Non-reentrancy lock for entrypoints that move CSPR or call external tokens
+/// Embed as a `SubModule` and wrap entrypoint bodies in `enter()` / `exit()`
+/// (a revert rolls the lock back with the rest of the state, so failures must
+/// revert through `env().revert` rather than panic)
+#[odra::module]
+pub struct ReentrancyGuard {
+    locked: Var<bool>,
+}
+
+#[odra::module]
+impl ReentrancyGuard {
+    /// Check if a guarded entrypoint is currently executing
+    pub fn is_locked(&self) -> bool {
+        self.locked.get_or_default()
+    }
+}
+
+impl ReentrancyGuard {
+    /// Take the lock; reverts with `ReentrantCall` if it is already held
+    pub fn enter(&mut self) {
+        if self.locked.get_or_default() {
+            self.env().revert(SecurityError::ReentrantCall);
+        }
+        self.locked.set(true);
+    }
+
+    /// Release the lock
+    pub fn exit(&mut self) {
+        self.locked.set(false);
+    }
+}